@@ -2,6 +2,10 @@ import Prism, { languages } from 'prismjs'
 
 export const meowScriptSyntax = languages.extend('clike', {
   comment: [
+    {
+      pattern: /\/\*[\s\S]*?\*\//,
+      greedy: true
+    },
     {
       pattern: /\/\//
     },
@@ -15,11 +19,11 @@ export const meowScriptSyntax = languages.extend('clike', {
     pattern: /(["`])(?:\\[\s\S]|(?!\1)[^\\])*\1/,
     greedy: true
   },
-  keyword: /\b(?:scratch|amew|pawction|purrhaps|meowtually|tail|pawckage|purrfect|clawful|furreal|furrever|hiss|continue)\b/,
+  keyword: /\b(?:scratch|amew|pawction|purrhaps|meowtually|tail|pawckage|purrfect|clawful|furreal|furrever|purrsist|fur|hiss|continue|attempurr|catch|finurrly|hairball)\b/,
   boolean: /\b(?:BUGGER ALL|NAH|YEAH)\b/,
-  number: /(?:(?:\b\d+(?:\.\d*)?|\B\.\d+)(?:e[-+]?\d+)?)i?/i,
+  number: /\b0x[\da-f_]+\b|\b0b[01_]+\b|\b0o[0-7_]+\b|(?:\b\d[\d_]*(?:\.\d[\d_]*)?)(?:e[-+]?\d[\d_]*)?/i,
   operator:
-    /[*/%^!=]=?|~|\+[=+]?|-[=-]?|\|[=|]?|&(?:=|&|\^=?)?|>(?:>=?|=)?|<(?:<=?|=|-)?|:=|\.\.\./
+    /[*/%^!=]=?|~|\+[=+]?|-[=-]?|\|[=|]?|&(?:=|&|\^=?)?|>(?:>=?|=)?|<(?:<=?|=|-)?|:=|\.\.[.=]?/
 })
 
 Prism.languages.meowScript = meowScriptSyntax
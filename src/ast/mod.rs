@@ -1,5 +1,7 @@
 pub mod token;
+use num_bigint::BigInt;
 use std::fmt::{self, Display, Formatter};
+use token::Span;
 
 #[derive(PartialEq, Clone, Debug)]
 pub struct Ident(pub String);
@@ -30,10 +32,14 @@ pub enum Infix {
     AND,
     OR,
     XOR,
+    LogicalAnd,
+    LogicalOr,
 }
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum Literal {
+    Int(i64),
+    BigInt(BigInt),
     Number(f64),
     String(String),
     Boolean(bool),
@@ -42,38 +48,64 @@ pub enum Literal {
 }
 
 #[derive(PartialEq, Clone, Debug)]
-pub enum Statement {
-    Set(Ident, Expr),
-    Return(Expr),
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum StatementKind {
+    /// `scratch name = value;`, with the `///` doc comment written above it.
+    Set(Ident, Expr, Option<String>),
+    Return(Option<Expr>),
     Expression(Expr),
     Include(String),
-    Anew(Ident, Expr),
-    Break,
-    Continue
+    /// `amew target = value;`, where the target is a variable, an index like
+    /// `arr[0]` or a property like `cat's name`, possibly nested.
+    /// Compound assignments like `x += 1` carry the operator that combines
+    /// the current value of the target with `value`.
+    Anew(Expr, Option<Infix>, Expr),
+    /// `hiss 'label value;`, both the label and the value are optional.
+    Break(Option<String>, Option<Expr>),
+    /// `continue 'label;`, the label is optional.
+    Continue(Option<String>),
+    Throw(Expr)
 }
 
+/// How tightly an operator binds, from loosest to tightest.
+/// The order follows Rust: bitwise operators bind tighter than comparisons,
+/// so `a & b == c` is `(a & b) == c`, and shifts bind looser than arithmetic,
+/// so `x << 1 + 2` is `x << (1 + 2)`. Ranges bind just tighter than
+/// comparisons, so `x ~ 0..n + 1` is `x ~ (0..(n + 1))`.
 #[derive(PartialEq, PartialOrd, Debug, Clone)]
 pub enum Precedence {
     Lowest,
+    LogicalOr,
+    LogicalAnd,
     Equals,
     LessGreater,
+    Range,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Shift,
     Sum,
     Product,
     Prefix,
     Call,
     Index,
-    In,
-    LeftShift,
-    RightShift,
-    AND,
-    OR,
-    XOR
 }
 
 pub type BlockStatement = Vec<Statement>;
 
 #[derive(PartialEq, Clone, Debug)]
-pub enum Expr {
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum ExprKind {
     Literal(Literal),
     Ident(Ident),
     Prefix(Prefix, Box<Expr>),
@@ -99,21 +131,91 @@ pub enum Expr {
         index: Box<Expr>
     },
 
+    /// `array[start:end:step]`, any of which can be left out.
+    Slice {
+        array: Box<Expr>,
+        start: Option<Box<Expr>>,
+        end: Option<Box<Expr>>,
+        step: Option<Box<Expr>>
+    },
+
     Typeof {
         expr: Box<Expr>
     },
 
+    /// `furrever { ... }`. Loops can be labeled with `'label:` in front of them.
     Loop {
-        body: BlockStatement
+        body: BlockStatement,
+        label: Option<String>
+    },
+
+    /// `purrsist (cond) { ... }`, runs the body for as long as `cond` is truthy.
+    While {
+        cond: Box<Expr>,
+        body: BlockStatement,
+        label: Option<String>
+    },
+
+    /// `fur (binding ~ iterable) { ... }`, runs the body once for every
+    /// element of an array, character of a string or key of an object.
+    For {
+        binding: Ident,
+        iterable: Box<Expr>,
+        body: BlockStatement,
+        label: Option<String>
+    },
+
+    /// A template string; its parts are string literals and embedded expressions.
+    Template(Vec<Expr>),
+
+    /// `start..end`, or `start..=end` when `inclusive`.
+    Range {
+        start: Box<Expr>,
+        end: Box<Expr>,
+        inclusive: bool
+    },
+
+    Try {
+        body: BlockStatement,
+        catch: Option<(Option<Ident>, BlockStatement)>,
+        finally: Option<BlockStatement>
     }
 
 }
 
+impl Statement {
+    pub fn new(kind: StatementKind, span: Span) -> Self {
+        Statement { kind, span }
+    }
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Self {
+        Expr { kind, span }
+    }
+}
+
 #[derive(PartialEq, Clone, Debug)]
 pub struct Program {
     pub(crate) statements: Vec<Statement>
 }
 
+impl Program {
+    /// ## docs
+    /// The top-level `scratch` bindings that have a doc comment.
+    /// # Returns
+    /// `Vec<(&str, &str)>` - The name and doc comment of each binding, in source order.
+    pub fn docs(&self) -> Vec<(&str, &str)> {
+        self.statements
+            .iter()
+            .filter_map(|stmt| match stmt.kind {
+                StatementKind::Set(Ident(ref name), _, Some(ref doc)) => Some((name.as_str(), doc.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
 // Implement the Display trait for all the types we have
 
 impl Display for Prefix {
@@ -145,13 +247,15 @@ impl Display for Infix {
             Infix::RightShift => write!(f, ">>"),
             Infix::AND => write!(f, "&"),
             Infix::OR => write!(f, "|"),
-            Infix::XOR => write!(f, "^")
+            Infix::XOR => write!(f, "^"),
+            Infix::LogicalAnd => write!(f, "&&"),
+            Infix::LogicalOr => write!(f, "||")
         }
     }
 }
 
 impl Display for Expr {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        write!(f, "{:?}", self.kind)
     }
 }
\ No newline at end of file
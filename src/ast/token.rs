@@ -1,15 +1,70 @@
-use std::fmt::{self, Display, Formatter};
+use num_bigint::BigInt;
+use std::{
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+/// ## Span
+/// The location of a token or node in the source.
+/// `offset` and `len` are in bytes, `line` and `column` start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub file: Rc<str>,
+    pub offset: usize,
+    pub len: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    /// Creates a span that starts where `self` starts and ends where `end` ends.
+    pub fn to(&self, end: &Span) -> Span {
+        let stop = (end.offset + end.len).max(self.offset);
+        Span {
+            file: self.file.clone(),
+            offset: self.offset,
+            len: stop - self.offset,
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// ## TemplatePart
+/// A piece of a template string: literal text, or the source of an
+/// embedded `${...}` expression along with the byte offset, line and column
+/// it starts at in the file the template came from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplatePart {
+    Text(String),
+    Code {
+        source: String,
+        offset: usize,
+        line: usize,
+        column: usize,
+    },
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
     Eof,
     Illegal,
-    Comment,
+    /// One line of a `///` doc comment, without the slashes.
+    DocComment(String),
 
     // Literals
     Ident(String),
+    Int(i64),
+    BigInt(BigInt),
     Number(f64),
     String(String),
+    Template(Vec<TemplatePart>),
     Boolean(bool),
 
     // Operators
@@ -22,6 +77,12 @@ pub enum Token {
     Percent,
     Anew,
     In,
+    DotDot,
+    DotDotEq,
+
+    // Logical operators
+    LogicalAnd,
+    LogicalOr,
 
     // Bitwise operators
     AND,
@@ -30,6 +91,20 @@ pub enum Token {
     LeftShift,
     RightShift,
 
+    // Compound assignment
+    PlusAssign,
+    MinusAssign,
+    AsteriskAssign,
+    SlashAssign,
+    PercentAssign,
+    AndAssign,
+    OrAssign,
+    XorAssign,
+    LeftShiftAssign,
+    RightShiftAssign,
+    Increment,
+    Decrement,
+
     // Comparison
     Less,
     Greater,
@@ -49,6 +124,8 @@ pub enum Token {
     LeftBracket,
     RightBracket,
     SingleQuoteS,
+    /// A loop label such as `'outer`, without the quote.
+    Label(String),
 
     // Keywords
     Set,
@@ -59,8 +136,14 @@ pub enum Token {
     Include,
     Typeof,
     Loop,
+    While,
+    For,
     Break,
-    Continue
+    Continue,
+    Try,
+    Catch,
+    Finally,
+    Throw
 
 }
 
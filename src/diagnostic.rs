@@ -0,0 +1,103 @@
+use crate::{error::Error, std_library::get_std_lib};
+use std::{collections::HashMap, fs};
+
+/// ## Sources
+/// Keeps the text of every file a program was read from,
+/// so diagnostics can quote the line an error points at.
+/// Files that were never added are looked up on demand:
+/// `nya:` names resolve to the standard library prelude,
+/// anything else is read from disk.
+#[derive(Default)]
+pub struct Sources {
+    files: HashMap<String, String>,
+}
+
+impl Sources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, file: &str, text: &str) {
+        self.files.insert(file.to_string(), text.to_string());
+    }
+
+    pub fn get(&mut self, file: &str) -> Option<&str> {
+        if !self.files.contains_key(file) {
+            let text = if file.starts_with("nya:") {
+                get_std_lib(file.to_string()).and_then(|lib| lib.raw)
+            } else {
+                fs::read_to_string(file).ok()
+            }?;
+            self.files.insert(file.to_string(), text);
+        }
+        self.files.get(file).map(|s| s.as_str())
+    }
+}
+
+/// ## render
+/// Renders an error the way rustc does:
+/// the message, the location, the offending source line with the span underlined,
+/// any notes or help attached to the error, and its pawction backtrace.
+/// # Arguments
+/// * `error` - The error to render.
+/// * `sources` - Where to look up the text of the file the error points into.
+/// # Returns
+/// `String` - The rendered diagnostic, without a trailing newline.
+/// # Examples
+/// ```text
+/// error: 😾 Meow-sterious! The identifier 'y' seems to be playing a game of hide-and-seek
+///  --> main.meow:2:14
+///   |
+/// 2 |     tail x + y;
+///   |              ^
+/// ```
+pub fn render(error: &Error, sources: &mut Sources) -> String {
+    let mut out = format!("error: {}", error.kind);
+    let mut gutter = String::from(" ");
+
+    if let Some(ref span) = error.span {
+        let snippet = sources
+            .get(&span.file)
+            .and_then(|text| snippet(text, span.offset, span.len));
+        if let Some((line, pad, marks)) = snippet {
+            gutter = " ".repeat(span.line.to_string().len());
+            out.push_str(&format!("\n{}--> {}", gutter, span));
+            out.push_str(&format!("\n{} |", gutter));
+            out.push_str(&format!("\n{} | {}", span.line, line));
+            out.push_str(&format!("\n{} | {}{}", gutter, " ".repeat(pad), "^".repeat(marks)));
+        } else {
+            out.push_str(&format!("\n{}--> {}", gutter, span));
+        }
+    }
+
+    for note in error.notes.iter() {
+        out.push_str(&format!("\n{} = note: {}", gutter, note));
+    }
+    if let Some(ref help) = error.help {
+        out.push_str(&format!("\n{} = help: {}", gutter, help));
+    }
+    if !error.backtrace.is_empty() {
+        out.push_str(&format!("\n{} = backtrace:", gutter));
+        for (i, frame) in error.backtrace.iter().enumerate() {
+            out.push_str(&format!("\n{}     {}: {}", gutter, i, frame));
+        }
+    }
+
+    out
+}
+
+/// Finds the line containing `offset` in `text`.
+/// Returns the line with tabs expanded, the number of columns before the span
+/// and the number of columns the span covers on that line (at least one).
+fn snippet(text: &str, offset: usize, len: usize) -> Option<(String, usize, usize)> {
+    let start = text.get(..offset)?.rfind('\n').map_or(0, |i| i + 1);
+    let end = text.get(offset..)?.find('\n').map_or(text.len(), |i| offset + i);
+    let stop = (offset + len).min(end);
+
+    let width = |s: &str| s.chars().map(|c| if c == '\t' { 4 } else { 1 }).sum::<usize>();
+    let line = text[start..end].trim_end_matches('\r').replace('\t', "    ");
+    let pad = width(&text[start..offset]);
+    let marks = text.get(offset..stop).map_or(0, width).max(1);
+
+    Some((line, pad, marks))
+}
@@ -0,0 +1,229 @@
+use crate::{ast::token::Span, evaluation::object::Object};
+use std::fmt::{self, Display, Formatter};
+
+/// ## ErrorKind
+/// What went wrong while parsing or evaluating a program.
+/// Embedders can match on the kind instead of the message,
+/// which is derived from it by the `Display` impl.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    /// The parser found `found` where it expected `expected`.
+    UnexpectedToken { expected: String, found: String },
+    /// A string or template literal ran into the end of the input.
+    UnterminatedString,
+    /// A number literal that could not be read, such as `1.2.3` or `0b102`.
+    MalformedNumber(String),
+    /// A `/*` block comment was never closed.
+    UnterminatedComment,
+    /// A `\` in a string literal was followed by something that is not an escape.
+    InvalidEscape(String),
+    /// A variable was used before it was declared with `scratch`.
+    UnknownIdentifier(String),
+    /// `hiss 'label` or `continue 'label` outside of a loop with that label.
+    UnknownLabel(String),
+    /// `hiss` or `continue` outside of any loop, including in a pawction called from one.
+    OutsideLoop(String),
+    /// An operator was applied to values it does not support.
+    UnknownOperator(String),
+    /// A value of the wrong type was given to an operation or builtin.
+    TypeMismatch(String),
+    /// A function was called with the wrong number of arguments.
+    Arity { expected: usize, found: usize },
+    /// A function that takes a varying number of arguments got too few or too many.
+    ArityBetween { min: usize, max: usize, found: usize },
+    /// An index fell outside of an array or string.
+    IndexOutOfRange { index: i64, len: usize },
+    /// An arithmetic operation with no meaningful result, such as shifting by a negative amount,
+    /// or one whose result is too big to compute.
+    Arithmetic(String),
+    /// A value that cannot be used as an object key.
+    UnhashableKey(String),
+    /// Something that is not a pawction was called.
+    NotCallable(String),
+    /// A `pawckage` could not be found.
+    UnknownLibrary(String),
+    /// Reading or writing a file failed.
+    IoError(String),
+    /// Raised by a script with `hairball`. `kind` is the name scripts see
+    /// when they catch it, and `payload` is any extra value thrown along.
+    Thrown {
+        kind: String,
+        message: String,
+        payload: Option<Box<Object>>,
+    },
+}
+
+impl ErrorKind {
+    /// The name of the kind, as seen by scripts that catch the error.
+    pub fn name(&self) -> &str {
+        match self {
+            ErrorKind::UnexpectedToken { .. } => "UnexpectedToken",
+            ErrorKind::UnterminatedString => "UnterminatedString",
+            ErrorKind::MalformedNumber(_) => "MalformedNumber",
+            ErrorKind::UnterminatedComment => "UnterminatedComment",
+            ErrorKind::InvalidEscape(_) => "InvalidEscape",
+            ErrorKind::UnknownIdentifier(_) => "UnknownIdentifier",
+            ErrorKind::UnknownLabel(_) => "UnknownLabel",
+            ErrorKind::OutsideLoop(_) => "OutsideLoop",
+            ErrorKind::UnknownOperator(_) => "UnknownOperator",
+            ErrorKind::TypeMismatch(_) => "TypeMismatch",
+            ErrorKind::Arity { .. } | ErrorKind::ArityBetween { .. } => "Arity",
+            ErrorKind::IndexOutOfRange { .. } => "IndexOutOfRange",
+            ErrorKind::Arithmetic(_) => "Arithmetic",
+            ErrorKind::UnhashableKey(_) => "UnhashableKey",
+            ErrorKind::NotCallable(_) => "NotCallable",
+            ErrorKind::UnknownLibrary(_) => "UnknownLibrary",
+            ErrorKind::IoError(_) => "IoError",
+            ErrorKind::Thrown { kind, .. } => kind,
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "Meowch! Expected {}, but got {}", expected, found)
+            }
+            ErrorKind::UnterminatedString => {
+                write!(f, "Meowch! These whiskers were never closed, the string is missing its closing quote")
+            }
+            ErrorKind::MalformedNumber(text) => {
+                write!(f, "Meowch! '{}' is not a number I can count on my paws", text)
+            }
+            ErrorKind::UnterminatedComment => {
+                write!(f, "Meowch! This comment was never closed, add a */ to end it")
+            }
+            ErrorKind::InvalidEscape(escape) => {
+                write!(f, "Meowch! \\{} is not an escape sequence I know", escape)
+            }
+            ErrorKind::UnknownLabel(label) => {
+                write!(f, "Meowch! There is no loop labeled '{} around here to jump to", label)
+            }
+            ErrorKind::OutsideLoop(keyword) => write!(f, "Meowch! `{}` only works inside a loop", keyword),
+            ErrorKind::UnknownIdentifier(name) => write!(
+                f,
+                "😾 Meow-sterious! The identifier '{}' seems to be playing a game of hide-and-seek",
+                name
+            ),
+            ErrorKind::UnknownOperator(op) => write!(f, "Hiss! Unknown operator: {}", op),
+            ErrorKind::TypeMismatch(what) => write!(f, "Hiss! Type mismatch: {}", what),
+            ErrorKind::Arity { expected, found } => write!(
+                f,
+                "Mrrp? Wrong number of arguments. Got {}. Expected {}.",
+                found, expected
+            ),
+            ErrorKind::ArityBetween { min, max, found } => write!(
+                f,
+                "Mrrp? Wrong number of arguments. Got {}. Expected {} to {}.",
+                found, min, max
+            ),
+            ErrorKind::IndexOutOfRange { index, len } => write!(
+                f,
+                "Paws off! Index {} is out of range for length {}",
+                index, len
+            ),
+            ErrorKind::Arithmetic(what) => write!(f, "Hiss! Bad math: {}", what),
+            ErrorKind::UnhashableKey(key) => {
+                write!(f, "Hiss! {} cannot be used as an object key, only numbers, strings, booleans, null and arrays of them can", key)
+            }
+            ErrorKind::NotCallable(what) => write!(
+                f,
+                "🙀 Cat-tastrophe alert! The function '{}' is nowhere to be found, like a clever cat hiding in plain sight",
+                what
+            ),
+            ErrorKind::UnknownLibrary(lib) => {
+                write!(f, "Meowch! Could not find the pawckage '{}'", lib)
+            }
+            ErrorKind::IoError(why) => write!(f, "Hairball! {}", why),
+            ErrorKind::Thrown { message, .. } => write!(f, "🙀 Coughed up a hairball: {}", message),
+        }
+    }
+}
+
+/// ## Frame
+/// A pawction call on the evaluator's call stack.
+/// Errors that escape a call keep a copy of these as their backtrace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    /// The name the pawction was called by, if it was called through one.
+    pub name: String,
+    /// Where the call was made.
+    pub call_site: Span,
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} called at {}", self.name, self.call_site)
+    }
+}
+
+/// ## Error
+/// An error raised while parsing or evaluating a program.
+/// It carries the span of the source that caused it, when one is known,
+/// along with any notes or help the diagnostic renderer should print
+/// and the calls that were active when it was raised, innermost first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub span: Option<Span>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+    pub backtrace: Vec<Frame>,
+}
+
+impl Error {
+    /// Creates an error that points at `span`.
+    pub fn at(kind: ErrorKind, span: Span) -> Self {
+        Error {
+            span: Some(span),
+            ..Error::from(kind)
+        }
+    }
+
+    /// The message shown to the user, derived from the kind.
+    pub fn message(&self) -> String {
+        self.kind.to_string()
+    }
+
+    /// Adds a note explaining the error.
+    pub fn with_note(mut self, note: String) -> Self {
+        self.notes.push(note);
+        self
+    }
+
+    /// Adds a suggestion for fixing the error.
+    pub fn with_help(mut self, help: String) -> Self {
+        self.help = Some(help);
+        self
+    }
+
+    /// Attaches `span` to the error unless it already has a location.
+    pub fn locate(mut self, span: &Span) -> Self {
+        if self.span.is_none() {
+            self.span = Some(span.clone());
+        }
+        self
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Error {
+            kind,
+            span: None,
+            notes: vec![],
+            help: None,
+            backtrace: vec![],
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.span {
+            Some(ref span) => write!(f, "{}: {}", span, self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
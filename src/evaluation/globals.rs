@@ -1,4 +1,6 @@
 use super::object::*;
+use crate::error::ErrorKind;
+use indexmap::IndexMap;
 use std::collections::HashMap;
 
 /// Adds the built-in functions to the global environment.
@@ -8,6 +10,8 @@ pub fn new_globals() -> HashMap<String, Object> {
     let mut globals = HashMap::new();
     globals.insert(String::from("meow"), Object::Inbuilt(meow));
     globals.insert(String::from("log"), Object::Inbuilt(log));
+    globals.insert(String::from("error"), Object::Inbuilt(error));
+    globals.insert(String::from("same"), Object::Inbuilt(same));
     globals
 }
 
@@ -17,7 +21,7 @@ pub fn new_globals() -> HashMap<String, Object> {
 /// It returns `null`.
 fn log(args: Vec<Object>) -> Object {
     if args.is_empty() {
-        return Object::Error(String::from("Wrong number of arguments"));
+        return Object::Error(ErrorKind::Arity { expected: 1, found: 0 }.into());
     } else {
         for arg in args {
             print!("{} ", arg);
@@ -29,7 +33,7 @@ fn log(args: Vec<Object>) -> Object {
 
 fn meow(args: Vec<Object>) -> Object {
     if args.is_empty() {
-        return Object::Error(String::from("Wrong number of arguments"));
+        return Object::Error(ErrorKind::Arity { expected: 1, found: 0 }.into());
     } else {
         print!("Meow!");
         for arg in args {
@@ -38,4 +42,31 @@ fn meow(args: Vec<Object>) -> Object {
         println!();
     }
     Object::Null
-}
\ No newline at end of file
+}
+
+/// The built-in function `error`.
+/// It builds an error object from a message and an optional payload,
+/// ready to be thrown with `hairball`.
+/// It returns the object, it does not raise it.
+fn error(args: Vec<Object>) -> Object {
+    if args.is_empty() || args.len() > 2 {
+        return Object::Error(ErrorKind::ArityBetween { min: 1, max: 2, found: args.len() }.into());
+    }
+    let mut hash = IndexMap::new();
+    hash.insert(Object::String(String::from("message")), Object::String(args[0].to_string()));
+    hash.insert(Object::String(String::from("kind")), Object::String(String::from("Hairball")));
+    if let Some(payload) = args.get(1) {
+        hash.insert(Object::String(String::from("payload")), payload.clone());
+    }
+    Object::map(hash)
+}
+/// The built-in function `same`.
+/// It checks whether its two arguments are the very same value.
+/// Unlike `==`, two arrays or objects with equal contents are only
+/// the same if changing one would change the other.
+fn same(args: Vec<Object>) -> Object {
+    if args.len() != 2 {
+        return Object::Error(ErrorKind::Arity { expected: 2, found: args.len() }.into());
+    }
+    Object::Bool(args[0].is_same(&args[1]))
+}
@@ -1,5 +1,5 @@
 use super::{store::Store, object::*, Eval};
-use crate::{lexer::Lexer, parser::Parser, std_library::*};
+use crate::{diagnostic::{render, Sources}, lexer::Lexer, parser::Parser, std_library::*};
 use std::{cell::RefCell, collections::HashMap, fs, rc::Rc};
 /// Function to load an external file or a standard library onto the environment.\
 /// The file is loaded as a string, and the string is parsed into an AST.
@@ -16,14 +16,14 @@ pub fn load_meow(lib: String) -> Option<HashMap<String, Object>> {
     if lib.starts_with("nya:") {
         // Loads the standard library.
         // The standard library is a HashMap of names to objects.
-        let libs = get_std_lib(lib).unwrap();
+        let libs = get_std_lib(lib.clone()).unwrap();
         let mut eval = Eval::new(Rc::new(RefCell::new(
             Store::from(libs.globals.clone())
         )));
 
         match &libs.raw {
             Some(s) => {
-                let mut parser = Parser::new(Lexer::new(s.to_string()));
+                let mut parser = Parser::new(Lexer::with_file(s.to_string(), &lib));
                 let program = parser.parse_program();
                 eval.eval(program);
                 let store = (&*eval.store.borrow()).to_owned().store;
@@ -41,12 +41,14 @@ pub fn load_meow(lib: String) -> Option<HashMap<String, Object>> {
     }
     let filename =format!("./{}.meow", lib);
     // File is read as a string.
-    let file = fs::read_to_string(filename).expect("Lib not found.");
-    let mut parser = Parser::new(Lexer::new(file));
+    let file = fs::read_to_string(&filename).expect("Lib not found.");
+    let mut sources = Sources::new();
+    sources.add(&filename, &file);
+    let mut parser = Parser::new(Lexer::with_file(file, &filename));
     let program = parser.parse_program();
     if !parser.errors.is_empty() {
         for e in parser.errors.iter() {
-            println!("\t{}", e);
+            println!("{}", render(e, &mut sources));
         }
         return None;
     };
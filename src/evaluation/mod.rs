@@ -3,11 +3,14 @@ pub mod store;
 pub mod library;
 pub mod globals;
 
-use crate::ast::*;
+use crate::{ast::{*, token::Span}, error::{Error, ErrorKind, Frame}};
 use globals::new_globals;
 use store::Store;
 use object::Object;
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use indexmap::IndexMap;
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+use std::{cell::RefCell, rc::Rc};
 
 use library::load_meow;
 
@@ -20,6 +23,8 @@ use library::load_meow;
 pub struct Eval {
     /// The current environment.
     pub store: Rc<RefCell<Store>>,
+    /// The pawction calls currently being evaluated, outermost first.
+    pub call_stack: Vec<Frame>,
 }
 
 impl Eval {
@@ -30,7 +35,10 @@ impl Eval {
     /// # Returns
     /// `Eval` - The new Eval struct.
     pub fn new(store: Rc<RefCell<Store>>) -> Self {
-        Eval { store }
+        Eval {
+            store,
+            call_stack: vec![],
+        }
     }
 
     /// ## is_truthy
@@ -60,7 +68,7 @@ impl Eval {
     /// ```
     /// use crate::eval::Eval;
     /// let eval = Eval::new(Rc::new(RefCell::new(Store::new())));
-    /// let obj = Object::Error(String::from("Error"));
+    /// let obj = Object::Error(ErrorKind::UnknownIdentifier(String::from("x")).into());
     /// assert_eq!(true, is_error(obj));
     /// ```
     fn is_error(&mut self, object: &Object) -> bool {
@@ -123,17 +131,22 @@ impl Eval {
     /// assert_eq!(Some(Object::Null), result);
     /// ```
     fn eval_statement(&mut self, statement: Statement) -> Option<Object> {
-        match statement {
-            Statement::Expression(e) => self.eval_expr(e),
-            Statement::Return(e) => {
-                let val = match self.eval_expr(e) {
-                    Some(v) => v,
-                    None => return None,
+        let span = statement.span;
+        match statement.kind {
+            StatementKind::Expression(e) => self.eval_expr(e),
+            StatementKind::Return(e) => {
+                let val = match e.map(|e| self.eval_expr(e)) {
+                    Some(Some(v)) => v,
+                    Some(None) => return None,
+                    None => Object::Null,
                 };
+                if self.is_error(&val) {
+                    return Some(val);
+                }
 
                 Some(Object::Return(Box::new(val)))
             }
-            Statement::Set(i, v) => {
+            StatementKind::Set(i, v, doc) => {
                 let val = match self.eval_expr(v) {
                     Some(value) => value,
                     None => return None,
@@ -142,12 +155,15 @@ impl Eval {
                     Some(val)
                 } else {
                     let Ident(name) = i;
-                    self.store.borrow_mut().set(name, val);
+                    let mut store = self.store.borrow_mut();
+                    if let Some(doc) = doc {
+                        store.document(name.clone(), doc);
+                    }
+                    store.set(name, val);
                     None
                 }
             }
-            Statement::Anew(i, v) => {
-                let Ident(name) = i;
+            StatementKind::Anew(target, infix, v) => {
                 let val = match self.eval_expr(v) {
                     Some(value) => value,
                     None => return None,
@@ -155,22 +171,32 @@ impl Eval {
                 if self.is_error(&val) {
                     Some(val)
                 } else {
-                    let mut store = self.store.borrow_mut();
-                    match store.get(&name) {
-                        Some(_) => {
-                            store.anew(name, val);
-                            None
-                        }
-                        None => Some(Object::Error(format!("Meow-sterious! The identifier '{}' seems to be playing a game of hide-and-seek", name))),
-                    }
+                    self.eval_assignment(target, infix, val, &span)
                 }
             }
-            Statement::Include(i) => {
+            StatementKind::Include(i) => {
                 let lib = i;
-                self.extend_global_store(lib)
+                self.extend_global_store(lib).map(|o| self.locate(o, &span))
             },
-            Statement::Break => Some(Object::Break),
-            Statement::Continue => Some(Object::Continue),
+            StatementKind::Break(label, value) => {
+                let value = match value.and_then(|v| self.eval_expr(v)) {
+                    Some(v) if self.is_error(&v) => return Some(v),
+                    Some(v) => v,
+                    None => Object::Null,
+                };
+                Some(Object::Break(label, Box::new(value)))
+            }
+            StatementKind::Continue(label) => Some(Object::Continue(label)),
+            StatementKind::Throw(e) => {
+                let val = match self.eval_expr(e) {
+                    Some(value) => value,
+                    None => return None,
+                };
+                if self.is_error(&val) {
+                    return Some(val);
+                }
+                Some(Object::Error(Error::at(self.thrown_kind(val), span)))
+            }
         }
     }
 
@@ -205,8 +231,7 @@ impl Eval {
             match self.eval_statement(statement) {
                 Some(Object::Return(e)) => return Some(Object::Return(e)),
                 Some(Object::Error(e)) => return Some(Object::Error(e)),
-                Some(Object::Break) => return Some(Object::Break),
-                Some(Object::Continue) => return Some(Object::Continue),
+                Some(o @ (Object::Break(..) | Object::Continue(_))) => return Some(o),
                 e => result = e,
             }
         }
@@ -232,13 +257,151 @@ impl Eval {
     /// assert_eq!(Some(Object::Null), result);
     /// ```
     fn eval_expr(&mut self, expr: Expr) -> Option<Object> {
+        let span = expr.span;
+        self.eval_expr_kind(expr.kind, &span).map(|o| self.locate(o, &span))
+    }
+
+    /// ## eval_assignment
+    /// Stores `val` in `target`, which is a variable or an index or property of one.
+    /// Arrays and objects are shared, so `amew arr[0]'s name = val` changes the
+    /// object in place and everything holding it sees the new name.
+    /// The indexes in `target` are evaluated once, even when a compound assignment
+    /// like `arr[f()] += 1` both reads and writes it.
+    /// # Arguments
+    /// * `target` - The variable, index or property being assigned to.
+    /// * `infix` - The operator of a compound assignment, which combines the current value with `val`.
+    /// * `val` - The value to assign.
+    /// * `span` - Where the assignment is, for errors.
+    /// # Returns
+    /// `Option<Object>` - An error if the assignment failed, `None` otherwise.
+    fn eval_assignment(&mut self, target: Expr, infix: Option<Infix>, val: Object, span: &Span) -> Option<Object> {
+        let mut indexes = vec![];
+        let mut root = target;
+        while let ExprKind::Index { array, index } = root.kind {
+            indexes.push(*index);
+            root = *array;
+        }
+        let name = match root.kind {
+            ExprKind::Ident(Ident(name)) => name,
+            _ => return Some(Object::Error(Error::at(ErrorKind::TypeMismatch(String::from("only variables can be assigned to")), span.clone()))),
+        };
+
+        // Keys are evaluated left to right, as they are written.
+        let mut keys = vec![];
+        for index in indexes.into_iter().rev() {
+            match self.eval_expr(index) {
+                Some(key) if self.is_error(&key) => return Some(key),
+                Some(key) => keys.push(key),
+                None => return None,
+            }
+        }
+
+        let current = self.store.borrow_mut().get(&name);
+        let mut container = match current {
+            Some(current) => current,
+            None => {
+                return Some(Object::Error(
+                    Error::at(ErrorKind::UnknownIdentifier(name.clone()), span.clone())
+                        .with_help(format!("`amew` only changes existing variables, use `scratch {} = ...;` to declare it", name)),
+                ))
+            }
+        };
+        let (last, path) = match keys.split_last() {
+            Some(keys) => keys,
+            None => {
+                let val = match infix {
+                    Some(infix) => self.eval_infix_expr(infix, container, val),
+                    None => val,
+                };
+                if self.is_error(&val) {
+                    return Some(self.locate(val, span));
+                }
+                self.store.borrow_mut().anew(name, val);
+                return None;
+            }
+        };
+        for key in path {
+            container = self.eval_index_expr(container, key.clone());
+            if self.is_error(&container) {
+                return Some(self.locate(container, span));
+            }
+        }
+        let val = match infix {
+            Some(infix) => {
+                let current = self.eval_index_expr(container.clone(), last.clone());
+                match current {
+                    Object::Error(_) => current,
+                    current => self.eval_infix_expr(infix, current, val),
+                }
+            }
+            None => val,
+        };
+        if self.is_error(&val) {
+            return Some(self.locate(val, span));
+        }
+        match self.set_index(container, last.clone(), val) {
+            Object::Error(e) => Some(self.locate(Object::Error(e), span)),
+            _ => None,
+        }
+    }
+
+    /// ## set_index
+    /// Puts `val` into `container` at `key`, changing it in place.
+    /// # Arguments
+    /// * `container` - The array or object to put the value into.
+    /// * `key` - The index or property to put it at. Arrays can only be
+    ///   changed at indexes they already have, objects get new properties added.
+    /// * `val` - The value to put there.
+    /// # Returns
+    /// `Object` - `Null`, or an error if `key` does not fit the container.
+    fn set_index(&mut self, container: Object, key: Object, val: Object) -> Object {
+        match container {
+            Object::Array(arr) => {
+                let index = match key.as_f64() {
+                    Some(i) => i as i64,
+                    None => return Object::Error(ErrorKind::TypeMismatch(format!("arrays are indexed by numbers, not by {}", key)).into()),
+                };
+                let len = arr.borrow().len();
+                let at = if index < 0 { index + len as i64 } else { index };
+                if at < 0 || at >= len as i64 {
+                    return Object::Error(ErrorKind::IndexOutOfRange { index, len }.into());
+                }
+                arr.borrow_mut()[at as usize] = val;
+                Object::Null
+            }
+            Object::Object(hash) => match key.as_key() {
+                Some(key) => {
+                    hash.borrow_mut().insert(key, val);
+                    Object::Null
+                }
+                None => Object::Error(ErrorKind::UnhashableKey(key.to_string()).into()),
+            },
+            o => Object::Error(ErrorKind::TypeMismatch(format!("{} is not an array or object, so [{}] cannot be set on it", o, key)).into()),
+        }
+    }
+
+    /// ## locate
+    /// Points an error that has no location yet at `span`.
+    /// Errors that already carry a span are passed through untouched,
+    /// so the innermost failing node is the one that gets reported.
+    fn locate(&mut self, object: Object, span: &Span) -> Object {
+        match object {
+            Object::Error(e) => Object::Error(e.locate(span)),
+            o => o,
+        }
+    }
+
+    fn eval_expr_kind(&mut self, expr: ExprKind, span: &Span) -> Option<Object> {
         match expr {
-            Expr::Ident(ident) => Some(self.eval_ident(ident)),
-            Expr::Literal(lit) => Some(self.eval_literal(lit)),
-            Expr::Prefix(prefix, right) => self
+            ExprKind::Ident(ident) => Some(self.eval_ident(ident)),
+            ExprKind::Literal(lit) => Some(self.eval_literal(lit)),
+            ExprKind::Prefix(prefix, right) => self
                 .eval_expr(*right)
                 .map(|expr| self.eval_prefix_expr(prefix, expr)),
-            Expr::Infix(infix, left, right) => {
+            ExprKind::Infix(infix @ (Infix::LogicalAnd | Infix::LogicalOr), left, right) => {
+                self.eval_logical_expr(infix, *left, *right)
+            }
+            ExprKind::Infix(infix, left, right) => {
                 let left_expr = self.eval_expr(*left);
                 let right_expr = self.eval_expr(*right);
                 match left_expr.clone() {
@@ -254,7 +417,7 @@ impl Eval {
                     _ => None,
                 }
             }
-            Expr::If {
+            ExprKind::If {
                 cond: condition,
                 then: consequence,
                 else_: alternative,
@@ -263,6 +426,9 @@ impl Eval {
                     Some(e) => e,
                     None => return None,
                 };
+                if self.is_error(&cond_expr) {
+                    return Some(cond_expr);
+                }
 
                 if self.is_truthy(cond_expr) {
                     self.eval_block_statement(*consequence)
@@ -272,46 +438,259 @@ impl Eval {
                     None
                 }
             }
-            Expr::Fun { params, body } => Some(Object::Fn(params, body, self.store.clone())),
-            Expr::Call { function, args } => Some(self.eval_call_expr(*function, args)),
-            Expr::Index { array, index } => {
+            ExprKind::Fun { params, body } => Some(Object::Fn(params, body, self.store.clone())),
+            ExprKind::Call { function, args } => Some(self.eval_call_expr(*function, args, span)),
+            ExprKind::Index { array, index } => {
                 let obj = self.eval_expr(*array);
                 let i = self.eval_expr(*index);
-                if let Some(Object::Object(obj)) = obj {
-                    let idx = match i {
-                        Some(Object::Number(i)) => Object::Number(i),
-                        Some(Object::String(i)) => Object::String(i),
-                        Some(Object::Bool(i)) => Object::Bool(i),
-                        _ => return None,
-                    };
-                    Some(self.eval_index_expr(Object::Object(obj.clone()), idx))
-                } else if let Some(Object::Array(arr)) = obj {
-                    let idx = match i {
-                        Some(Object::Number(i)) => Object::Number(i),
-                        _ => return None,
-                    };
-                    Some(self.eval_index_expr(Object::Array(arr.clone()), idx))
-                } else {
-                    None
+                match (obj, i) {
+                    (Some(e @ Object::Error(_)), _) | (_, Some(e @ Object::Error(_))) => Some(e),
+                    (Some(obj), Some(idx)) => Some(self.eval_index_expr(obj, idx)),
+                    _ => None,
                 }
             }
-            Expr::Typeof { expr } => Some(self.eval_typeof_expr(*expr)),
-
-            Expr::Loop { body }  => {
-                let mut _result = None;
-                loop {
-                    match self.eval_block_statement((*body).to_vec()) {
-                        Some(Object::Return(e)) => return Some(Object::Return(e)),
-                        Some(Object::Error(e)) => return Some(Object::Error(e)),
-                        Some(Object::Break) => {
-                            break Some(Object::Null);
-                        },
-                        Some(Object::Continue) => continue,
-                        e => _result = e,
-                    }
+            ExprKind::Slice { array, start, end, step } => {
+                let seq = self.eval_expr(*array)?;
+                let start = start.and_then(|e| self.eval_expr(*e));
+                let end = end.and_then(|e| self.eval_expr(*e));
+                let step = step.and_then(|e| self.eval_expr(*e));
+                Some(self.eval_slice_expr(seq, start, end, step))
+            }
+            ExprKind::Typeof { expr } => Some(self.eval_typeof_expr(*expr)),
+            ExprKind::Try { body, catch, finally } => self.eval_try_expr(body, catch, finally),
+            ExprKind::Template(parts) => Some(self.eval_template_expr(parts)),
+            ExprKind::Range { start, end, inclusive } => {
+                let start = self.eval_expr(*start)?;
+                let end = self.eval_expr(*end)?;
+                Some(self.eval_range_expr(start, end, inclusive))
+            }
+
+            ExprKind::Loop { body, label } => loop {
+                if let Some(result) = self.eval_loop_body(&body, &label) {
+                    break Some(result);
                 }
+            },
+            ExprKind::While { cond, body, label } => loop {
+                let cond = match self.eval_expr((*cond).clone()) {
+                    Some(c) if self.is_error(&c) => return Some(c),
+                    Some(c) => c,
+                    None => return None,
+                };
+                if !self.is_truthy(cond) {
+                    break Some(Object::Null);
+                }
+                if let Some(result) = self.eval_loop_body(&body, &label) {
+                    break Some(result);
+                }
+            },
+            ExprKind::For { binding, iterable, body, label } => self.eval_for_expr(binding, *iterable, body, label),
+        }
+    }
+
+    /// ## eval_loop_body
+    /// Runs one iteration of a loop body. A `continue` for this loop simply ends the iteration.
+    /// A `hiss` or `continue` with the label of a loop further out stops this loop
+    /// and is passed on until it reaches that loop.
+    /// # Arguments
+    /// * `body` - The body of the loop.
+    /// * `label` - The label of the loop, if it has one.
+    /// # Returns
+    /// `Option<Object>` - What the loop evaluates to if it has to stop: the value given
+    /// to `hiss` (`Null` without one), or the return value, error or jump to an outer
+    /// loop that ends it. `None` to keep looping.
+    fn eval_loop_body(&mut self, body: &BlockStatement, label: &Option<String>) -> Option<Object> {
+        match self.eval_block_statement(body.clone()) {
+            Some(Object::Break(to, value)) if to.is_none() || to == *label => Some(*value),
+            Some(Object::Continue(to)) if to.is_none() || to == *label => None,
+            Some(o @ (Object::Return(_) | Object::Error(_) | Object::Break(..) | Object::Continue(_))) => Some(o),
+            _ => None,
+        }
+    }
+
+    /// ## eval_for_expr
+    /// Evaluates `fur (binding ~ iterable) { ... }`.
+    /// The elements are taken before the first iteration, so changing the
+    /// array or object inside the body does not change what is looped over.
+    /// # Arguments
+    /// * `binding` - The variable each element is assigned to, in the current scope.
+    /// * `iterable` - An array, string, object or range.
+    /// * `body` - The body of the loop.
+    /// * `label` - The label of the loop, if it has one.
+    /// # Returns
+    /// `Option<Object>` - `Null` once the loop is done, or whatever ended it early, see `eval_loop_body`.
+    fn eval_for_expr(&mut self, binding: Ident, iterable: Expr, body: BlockStatement, label: Option<String>) -> Option<Object> {
+        let iterable = self.eval_expr(iterable)?;
+        let items: Box<dyn Iterator<Item = Object>> = match iterable {
+            Object::Array(ref arr) => Box::new(arr.borrow().clone().into_iter()),
+            // Keys are handed out as copies, so changing an array key in the loop cannot change the object.
+            Object::Object(ref hash) => Box::new(hash.borrow().keys().filter_map(Object::as_key).collect::<Vec<_>>().into_iter()),
+            Object::String(ref s) => Box::new(s.chars().map(|c| Object::String(c.to_string())).collect::<Vec<_>>().into_iter()),
+            Object::Range(start, end, inclusive) => Box::new(Object::ints(start, end, inclusive).map(Object::Int)),
+            Object::Error(_) => return Some(iterable),
+            o => return Some(Object::Error(ErrorKind::TypeMismatch(format!("cannot loop over {}, only over arrays, strings, objects and ranges", o)).into())),
+        };
+
+        let Ident(name) = binding;
+        for item in items {
+            self.store.borrow_mut().set(name.clone(), item);
+            if let Some(result) = self.eval_loop_body(&body, &label) {
+                return Some(result);
             }
         }
+        Some(Object::Null)
+    }
+
+    /// ## eval_range_expr
+    /// Evaluates `start..end` or `start..=end` into a range.
+    /// # Arguments
+    /// * `start` - The first integer in the range.
+    /// * `end` - The integer the range stops at.
+    /// * `inclusive` - Whether `end` is part of the range.
+    /// # Returns
+    /// `Object` - The range, or an error if the bounds are not integers.
+    fn eval_range_expr(&mut self, start: Object, end: Object, inclusive: bool) -> Object {
+        if self.is_error(&start) {
+            return start;
+        }
+        if self.is_error(&end) {
+            return end;
+        }
+        match (start.as_i64(), end.as_i64()) {
+            (Some(from), Some(to)) => Object::Range(from, to, inclusive),
+            _ => {
+                let op = if inclusive { "..=" } else { ".." };
+                Object::Error(ErrorKind::TypeMismatch(format!("ranges need whole numbers, got {}{}{}", start, op, end)).into())
+            }
+        }
+    }
+
+    /// ## eval_logical_expr
+    /// Evaluates `&&` and `||`, only evaluating the right side when it decides the result.
+    /// Like in JavaScript or Python the result is one of the operands rather than a boolean:
+    /// `a && b` is `a` if `a` is falsy and `b` otherwise, `a || b` is `a` if `a` is truthy and `b` otherwise.
+    /// # Arguments
+    /// * `infix` - `Infix::LogicalAnd` or `Infix::LogicalOr`.
+    /// * `left` - The left expression, always evaluated.
+    /// * `right` - The right expression, evaluated only when needed.
+    /// # Returns
+    /// `Option<Object>` - The result of the evaluation.
+    fn eval_logical_expr(&mut self, infix: Infix, left: Expr, right: Expr) -> Option<Object> {
+        let left = self.eval_expr(left)?;
+        if self.is_error(&left) || self.is_truthy(left.clone()) == (infix == Infix::LogicalOr) {
+            return Some(left);
+        }
+        self.eval_expr(right)
+    }
+
+    /// ## eval_template_expr
+    /// Evaluates a template string by joining the `Display` form of each part.
+    /// # Arguments
+    /// * `parts` - The literal text and embedded expressions, in order.
+    /// # Returns
+    /// `Object` - The joined string, or the first error an embedded expression produced.
+    fn eval_template_expr(&mut self, parts: Vec<Expr>) -> Object {
+        let mut result = String::new();
+        for part in parts {
+            match self.eval_expr(part) {
+                Some(Object::Error(e)) => return Object::Error(e),
+                Some(obj) => result.push_str(&obj.to_string()),
+                None => {}
+            }
+        }
+        Object::String(result)
+    }
+
+    /// ## eval_try_expr
+    /// Evaluates an `attempurr` expression.
+    /// If the body fails, the error is bound to the catch variable as an
+    /// object with its `message`, `kind` and location, and the catch block runs
+    /// in its own scope. The `finurrly` block always runs afterwards; if it
+    /// returns, breaks or fails itself, that replaces the result of the body.
+    /// # Arguments
+    /// * `body` - The block that may fail.
+    /// * `catch` - The optional binding and block that handle the failure.
+    /// * `finally` - The optional block that always runs.
+    /// # Returns
+    /// `Option<Object>` - The result of the body, or of the catch block if it ran.
+    fn eval_try_expr(
+        &mut self,
+        body: BlockStatement,
+        catch: Option<(Option<Ident>, BlockStatement)>,
+        finally: Option<BlockStatement>,
+    ) -> Option<Object> {
+        let mut result = self.eval_block_statement(body);
+
+        if let (Some(Object::Error(e)), Some((binding, handler))) = (&result, catch) {
+            let mut scope = Store::new_enclosed(self.store.clone());
+            if let Some(Ident(name)) = binding {
+                scope.set(name, self.error_object(e));
+            }
+            let current_store = std::mem::replace(&mut self.store, Rc::new(RefCell::new(scope)));
+            result = self.eval_block_statement(handler);
+            self.store = current_store;
+        }
+
+        if let Some(finally) = finally {
+            if let Some(o @ (Object::Return(_) | Object::Error(_) | Object::Break(..) | Object::Continue(_))) =
+                self.eval_block_statement(finally)
+            {
+                return Some(o);
+            }
+        }
+
+        result
+    }
+
+    /// ## error_object
+    /// Turns a caught error into an object scripts can inspect,
+    /// e.g. `e's message`, `e's kind` or `e's line`.
+    /// Errors raised with `hairball` also carry their `payload`.
+    fn error_object(&mut self, error: &Error) -> Object {
+        let (message, payload) = match error.kind {
+            ErrorKind::Thrown { ref message, ref payload, .. } => (message.clone(), payload.clone()),
+            _ => (error.message(), None),
+        };
+        let mut hash = IndexMap::new();
+        hash.insert(Object::String(String::from("message")), Object::String(message));
+        hash.insert(Object::String(String::from("kind")), Object::String(error.kind.name().to_string()));
+        if let Some(payload) = payload {
+            hash.insert(Object::String(String::from("payload")), *payload);
+        }
+        if let Some(ref span) = error.span {
+            hash.insert(Object::String(String::from("file")), Object::String(span.file.to_string()));
+            hash.insert(Object::String(String::from("line")), Object::Int(span.line as i64));
+            hash.insert(Object::String(String::from("column")), Object::Int(span.column as i64));
+        }
+        Object::map(hash)
+    }
+
+    /// ## thrown_kind
+    /// Works out what a `hairball` statement raises.
+    /// An object with a `message` (such as one built by `error()` or a caught error)
+    /// keeps its `kind` and `payload`, a string becomes the message,
+    /// and any other value is raised as the payload of a generic hairball.
+    fn thrown_kind(&mut self, value: Object) -> ErrorKind {
+        let key = |k: &str| Object::String(String::from(k));
+        match value {
+            Object::Object(ref hash) if hash.borrow().contains_key(&key("message")) => {
+                let hash = hash.borrow();
+                ErrorKind::Thrown {
+                    kind: hash.get(&key("kind")).map_or(String::from("Hairball"), |k| k.to_string()),
+                    message: hash[&key("message")].to_string(),
+                    payload: hash.get(&key("payload")).map(|p| Box::new(p.clone())),
+                }
+            }
+            Object::String(message) => ErrorKind::Thrown {
+                kind: String::from("Hairball"),
+                message,
+                payload: None,
+            },
+            value => ErrorKind::Thrown {
+                kind: String::from("Hairball"),
+                message: value.to_string(),
+                payload: Some(Box::new(value)),
+            },
+        }
     }
 
     fn eval_typeof_expr(&mut self, expr: Expr) -> Object {
@@ -319,10 +698,12 @@ impl Eval {
         match &obj.unwrap() {
             Object::Null => Object::String(String::from("null")),
             Object::Bool(_) => Object::String(String::from("boolean")),
+            Object::Int(_) | Object::BigInt(_) => Object::String(String::from("int")),
             Object::Number(_) => Object::String(String::from("number")),
             Object::String(_) => Object::String(String::from("string")),
             Object::Array(_) => Object::String(String::from("array")),
             Object::Object(_) => Object::String(String::from("object")),
+            Object::Range(..) => Object::String(String::from("range")),
             _ => Object::String(String::from("undefined")),
         }
 
@@ -380,8 +761,10 @@ impl Eval {
     /// ```
     fn eval_minus_prefix_expr(&mut self, expr: Object) -> Object {
         match expr {
+            Object::Int(i) => i.checked_neg().map_or_else(|| Object::integer(-BigInt::from(i)), Object::Int),
+            Object::BigInt(i) => Object::integer(-i),
             Object::Number(i) => Object::Number(-i),
-            _ => Object::Error(format!("unknown operator: -{}", expr)),
+            _ => Object::Error(ErrorKind::UnknownOperator(format!("-{}", expr)).into()),
         }
     }
 
@@ -395,8 +778,8 @@ impl Eval {
     /// `Error` - If the expression is not a number.
     fn eval_plus_prefix_expr(&mut self, expr: Object) -> Object {
         match expr {
-            Object::Number(i) => Object::Number(i),
-            _ => Object::Error(format!("unknown operator: {}", expr)),
+            Object::Int(_) | Object::BigInt(_) | Object::Number(_) => expr,
+            _ => Object::Error(ErrorKind::UnknownOperator(format!("+{}", expr)).into()),
         }
     }
 
@@ -414,24 +797,18 @@ impl Eval {
     /// `Error` - If the expression is not a number.
     fn eval_infix_expr(&mut self, infix: Infix, left: Object, right: Object) -> Object {
         match left {
-            Object::Number(left_expr) => {
-                if let Object::Number(right_expr) = right {
-                    self.eval_int_infix_expr(infix, left_expr, right_expr)
-                } else if let Object::Object(right_expr) = right {
-                    self.eval_object_infix_expr(infix, Object::Number(left_expr), Object::Object(right_expr))
-                } else {
-                    Object::Error(format!("type mismatch: {} {} {}", left, infix, right))
-                }
-            }
-            Object::String(left_expr) => {
-                if let Object::String(right_expr) = right {
-                    self.eval_string_infix_expr(infix, left_expr, right_expr)
-                } else if let Object::Object(right_expr) = right {
-                    self.eval_object_infix_expr(infix, Object::String(left_expr), Object::Object(right_expr))
-                } else {
-                    Object::Error(format!("type mismatch: {} {} {}", left_expr, infix, right))
+            Object::Int(_) | Object::BigInt(_) | Object::Number(_) => match right {
+                Object::Int(_) | Object::BigInt(_) | Object::Number(_) => self.eval_number_infix_expr(infix, left, right),
+                Object::Object(_) | Object::Array(_) | Object::Range(..) => self.eval_object_infix_expr(infix, left, right),
+                _ => Object::Error(ErrorKind::TypeMismatch(format!("{} {} {}", left, infix, right)).into()),
+            },
+            Object::String(left_expr) => match right {
+                Object::String(right_expr) => self.eval_string_infix_expr(infix, left_expr, right_expr),
+                Object::Object(_) | Object::Array(_) | Object::Range(..) => {
+                    self.eval_object_infix_expr(infix, Object::String(left_expr), right)
                 }
-            }
+                _ => Object::Error(ErrorKind::TypeMismatch(format!("{} {} {}", left_expr, infix, right)).into()),
+            },
             _ => self.eval_object_infix_expr(infix, left, right)
         }
     }
@@ -439,26 +816,173 @@ impl Eval {
     fn eval_string_infix_expr(&mut self, infix: Infix, left: String, right: String) -> Object {
         match infix {
             Infix::Plus => Object::String(format!("{}{}", left, right)),
-            _ => Object::Error(format!("unknown operator: {} {} {}", left, infix, right)),
+            _ => Object::Error(ErrorKind::UnknownOperator(format!("{} {} {}", left, infix, right)).into()),
         }
     }
 
     fn eval_object_infix_expr(&mut self, infix: Infix, left: Object, right: Object) -> Object {
         match infix {
             Infix::In => {
-                if let Object::Object(right) = right {
-                    Object::Bool(right.contains_key(&left))
-                } else if let Object::Array(right) = right {
-                    Object::Bool(right.contains(&left))
+                if let Object::Object(ref hash) = right {
+                    match left.as_key() {
+                        Some(key) => Object::Bool(hash.borrow().contains_key(&key)),
+                        None => Object::Error(ErrorKind::UnhashableKey(left.to_string()).into()),
+                    }
+                } else if let Object::Array(ref arr) = right {
+                    Object::Bool(arr.borrow().contains(&left))
+                } else if let Object::Range(start, end, inclusive) = right {
+                    Object::Bool(left.as_i64().is_some_and(|i| Object::ints(start, end, inclusive).contains(&i)))
                 } else {
-                    Object::Error(format!("unknown operator: {} {} {}", left, infix, right))
+                    Object::Error(ErrorKind::UnknownOperator(format!("{} {} {}", left, infix, right)).into())
                 }
             }
-            _ => Object::Error(format!("unknown operator: {} {} {}", left, infix, right)),
+            // Arrays and objects are equal when their contents are, see `same` for identity.
+            Infix::Equals => Object::Bool(left == right),
+            Infix::NotEquals => Object::Bool(left != right),
+            _ => Object::Error(ErrorKind::UnknownOperator(format!("{} {} {}", left, infix, right)).into()),
         }
     }
 
-    fn eval_int_infix_expr(&mut self, infix: Infix, left: f64, right: f64) -> Object {
+    /// ## eval_number_infix_expr
+    /// Evaluates an infix expression on two numbers.
+    /// Two integers stay integers, see `eval_int_infix_expr`.
+    /// If either side is a float the operation is done in floating point,
+    /// except for bitwise operators, which need both sides to be whole numbers.
+    /// # Arguments
+    /// * `infix` - The infix operator.
+    /// * `left` - The left number, an `Int`, `BigInt` or `Number`.
+    /// * `right` - The right number, an `Int`, `BigInt` or `Number`.
+    /// # Returns
+    /// `Object` - The result of the evaluation.
+    fn eval_number_infix_expr(&mut self, infix: Infix, left: Object, right: Object) -> Object {
+        if let (Object::Int(l), Object::Int(r)) = (&left, &right) {
+            return self.eval_int_infix_expr(infix, *l, *r);
+        }
+        if let (Some(l), Some(r)) = (left.as_big_int(), right.as_big_int()) {
+            return self.eval_big_int_infix_expr(infix, l, r);
+        }
+        match infix {
+            Infix::LeftShift | Infix::RightShift | Infix::AND | Infix::OR | Infix::XOR => {
+                match (left.as_i64(), right.as_i64()) {
+                    (Some(l), Some(r)) => self.eval_int_infix_expr(infix, l, r),
+                    _ => Object::Error(ErrorKind::TypeMismatch(format!(
+                        "{} needs whole numbers, got {} {} {}", infix, left, infix, right
+                    )).into()),
+                }
+            }
+            Infix::Equals | Infix::NotEquals => {
+                // Integers and floats compare exactly, and NaN is not equal to anything, not even itself.
+                let equal = left == right && !matches!(left, Object::Number(n) if n.is_nan());
+                Object::Bool((infix == Infix::Equals) == equal)
+            }
+            _ => match (left.as_f64(), right.as_f64()) {
+                (Some(l), Some(r)) => self.eval_float_infix_expr(infix, l, r),
+                _ => Object::Error(ErrorKind::TypeMismatch(format!("{} {} {}", left, infix, right)).into()),
+            },
+        }
+    }
+
+    /// ## eval_int_infix_expr
+    /// Evaluates an infix expression on two integers.
+    /// Arithmetic is exact: a result that overflows 64 bits, including a `<<` that shifts
+    /// bits out, is computed again with big integers by `eval_big_int_infix_expr`.
+    /// A `/` that does not divide evenly gives a float, so `7 / 2` is `3.5`,
+    /// and dividing by zero gives infinity or NaN, just like before integers existed.
+    /// `%` is the remainder of division rounded toward zero, so it takes the sign of `left`.
+    /// # Arguments
+    /// * `infix` - The infix operator.
+    /// * `left` - The left integer.
+    /// * `right` - The right integer.
+    /// # Returns
+    /// `Object` - The result of the evaluation.
+    fn eval_int_infix_expr(&mut self, infix: Infix, left: i64, right: i64) -> Object {
+        let result = match infix {
+            Infix::Plus => left.checked_add(right),
+            Infix::Minus => left.checked_sub(right),
+            Infix::Times => left.checked_mul(right),
+            Infix::Divide => match left.checked_rem(right) {
+                Some(0) => left.checked_div(right),
+                // `i64::MIN / -1` is the only division that overflows.
+                None if right != 0 => None,
+                _ => return Object::Number(left as f64 / right as f64),
+            },
+            Infix::Modulo if right == 0 => return Object::Number(left as f64 % right as f64),
+            Infix::Modulo => left.checked_rem(right),
+            Infix::LessThan => return Object::Bool(left < right),
+            Infix::LessThanEqual => return Object::Bool(left <= right),
+            Infix::GreaterThan => return Object::Bool(left > right),
+            Infix::GreaterThanEqual => return Object::Bool(left >= right),
+            Infix::Equals => return Object::Bool(left == right),
+            Infix::NotEquals => return Object::Bool(left != right),
+            Infix::In => return Object::Bool(left.to_string().contains(&right.to_string())),
+            // Short-circuited in `eval_logical_expr`, they never get here with values.
+            Infix::LogicalAnd | Infix::LogicalOr => {
+                return Object::Error(ErrorKind::UnknownOperator(format!("{} {} {}", left, infix, right)).into())
+            }
+            Infix::LeftShift => u32::try_from(right)
+                .ok()
+                .and_then(|s| left.checked_shl(s))
+                .filter(|n| n >> right == left),
+            Infix::RightShift => match u32::try_from(right) {
+                Ok(s) => Some(left >> s.min(63)),
+                Err(_) => None,
+            },
+            Infix::AND => Some(left & right),
+            Infix::OR => Some(left | right),
+            Infix::XOR => Some(left ^ right),
+        };
+        match result {
+            Some(n) => Object::Int(n),
+            None => self.eval_big_int_infix_expr(infix, BigInt::from(left), BigInt::from(right)),
+        }
+    }
+
+    /// ## eval_big_int_infix_expr
+    /// Evaluates an infix expression on two integers, either of which may be too big for an `Int`.
+    /// Follows the same rules as `eval_int_infix_expr`. Results that fit in an `Int` become one again.
+    /// # Arguments
+    /// * `infix` - The infix operator.
+    /// * `left` - The left integer.
+    /// * `right` - The right integer.
+    /// # Returns
+    /// `Object` - The result of the evaluation.
+    fn eval_big_int_infix_expr(&mut self, infix: Infix, left: BigInt, right: BigInt) -> Object {
+        let float = |n: &BigInt| n.to_f64().unwrap_or(f64::NAN);
+        match infix {
+            Infix::Plus => Object::integer(left + right),
+            Infix::Minus => Object::integer(left - right),
+            Infix::Times => Object::integer(left * right),
+            Infix::Divide if !right.is_zero() && (&left % &right).is_zero() => Object::integer(left / right),
+            Infix::Divide => Object::Number(float(&left) / float(&right)),
+            Infix::Modulo if right.is_zero() => Object::Number(f64::NAN),
+            Infix::Modulo => Object::integer(left % right),
+            Infix::LessThan => Object::Bool(left < right),
+            Infix::LessThanEqual => Object::Bool(left <= right),
+            Infix::GreaterThan => Object::Bool(left > right),
+            Infix::GreaterThanEqual => Object::Bool(left >= right),
+            Infix::Equals => Object::Bool(left == right),
+            Infix::NotEquals => Object::Bool(left != right),
+            Infix::In => Object::Bool(left.to_string().contains(&right.to_string())),
+            Infix::LogicalAnd | Infix::LogicalOr => {
+                Object::Error(ErrorKind::UnknownOperator(format!("{} {} {}", left, infix, right)).into())
+            }
+            Infix::LeftShift | Infix::RightShift => match usize::try_from(&right) {
+                Ok(s) if infix == Infix::LeftShift && !left.is_zero() && left.bits() + s as u64 > Object::MAX_INTEGER_BITS => {
+                    Object::Error(ErrorKind::Arithmetic(format!("{} << {} is too big to compute", left, right)).into())
+                }
+                Ok(s) if infix == Infix::LeftShift => Object::integer(left << s),
+                Ok(s) => Object::integer(left >> s),
+                Err(_) => Object::Error(
+                    ErrorKind::Arithmetic(format!("cannot shift {} by {} bits", left, right)).into(),
+                ),
+            },
+            Infix::AND => Object::integer(left & right),
+            Infix::OR => Object::integer(left | right),
+            Infix::XOR => Object::integer(left ^ right),
+        }
+    }
+
+    fn eval_float_infix_expr(&mut self, infix: Infix, left: f64, right: f64) -> Object {
         match infix {
             Infix::Plus => Object::Number(left + right),
             Infix::Minus => Object::Number(left - right),
@@ -472,54 +996,135 @@ impl Eval {
             Infix::Equals => Object::Bool(left == right),
             Infix::NotEquals => Object::Bool(left != right),
             Infix::In => Object::Bool(left.to_string().contains(&right.to_string())),
-            Infix::LeftShift => Object::Number(((left as i64) << (right as i64)) as f64),
-            Infix::RightShift => Object::Number(((left as i64) >> (right as i64)) as f64),
-            Infix::AND => Object::Number((left as i64 & right as i64) as f64),
-            Infix::OR => Object::Number((left as i64 | right as i64) as f64),
-            Infix::XOR => Object::Number((left as i64 ^ right as i64) as f64)
+            _ => Object::Error(ErrorKind::UnknownOperator(format!("{} {} {}", left, infix, right)).into()),
         }
     }
 
-    fn eval_call_expr(&mut self, function: Expr, args: Vec<Expr>) -> Object {
-        let args = args
-            .iter()
-            .map(|a| self.eval_expr(a.clone()).unwrap_or(Object::Null))
-            .collect::<Vec<_>>();
+    fn eval_call_expr(&mut self, function: Expr, args: Vec<Expr>, span: &Span) -> Object {
+        // An argument that failed fails the whole call, so it can be caught.
+        match self.eval_exprs(args) {
+            Ok(args) => self.apply_function(function, args, span),
+            Err(e) => *e,
+        }
+    }
 
-        self.apply_function(function, args)
+    /// ## eval_exprs
+    /// Evaluates `exprs` in order, stopping at the first one that fails
+    /// so nothing after it runs.
+    /// # Arguments
+    /// * `exprs` - The expressions to evaluate, such as call arguments or array elements.
+    /// # Returns
+    /// `Result<Vec<Object>, Box<Object>>` - The values, or the error that stopped them.
+    fn eval_exprs(&mut self, exprs: Vec<Expr>) -> Result<Vec<Object>, Box<Object>> {
+        let mut values = Vec::with_capacity(exprs.len());
+        for expr in exprs {
+            let value = self.eval_expr(expr).unwrap_or(Object::Null);
+            if self.is_error(&value) {
+                return Err(Box::new(value));
+            }
+            values.push(value);
+        }
+        Ok(values)
     }
 
     fn eval_index_expr(&mut self, left: Object, index: Object) -> Object {
         match left {
             Object::Array(ref arr) => {
-                if let Object::Number(i) = index {
-                    self.eval_array_index_expr(arr.clone(), i)
+                if let Object::Range(start, end, inclusive) = index {
+                    let (from, to) = slice_bounds(arr.borrow().len(), start, end, inclusive);
+                    Object::array(arr.borrow()[from..to].to_vec())
+                } else if let Some(i) = index.as_f64() {
+                    self.eval_array_index_expr(&arr.borrow(), i as i64)
+                } else {
+                    Object::Error(ErrorKind::UnknownOperator(format!("{}[{}]", left, index)).into())
+                }
+            }
+            Object::String(ref s) => {
+                let chars = s.chars().collect::<Vec<_>>();
+                if let Object::Range(start, end, inclusive) = index {
+                    let (from, to) = slice_bounds(chars.len(), start, end, inclusive);
+                    Object::String(chars[from..to].iter().collect())
+                } else if let Some(i) = index.as_f64() {
+                    let len = chars.len() as i64;
+                    let i = if i < 0.0 { i as i64 + len } else { i as i64 };
+                    match usize::try_from(i).ok().and_then(|i| chars.get(i)) {
+                        Some(c) => Object::String(c.to_string()),
+                        None => Object::Null,
+                    }
                 } else {
-                    Object::Error(format!("index operator not supported: {}", left))
+                    Object::Error(ErrorKind::UnknownOperator(format!("{}[{}]", left, index)).into())
                 }
             }
-            Object::Object(ref hash) => match index {
-                Object::Number(_) | Object::Bool(_) | Object::String(_) => match hash.get(&index) {
-                    Some(o) => o.clone(),
-                    None => {
-                        Object::Null
-                    },
-                },
-                Object::Error(_) => index,
-                _ => Object::Error(format!("unsable as hash key: {}", index)),
+            Object::Object(ref hash) => match index.as_key() {
+                Some(key) => hash.borrow().get(&key).cloned().unwrap_or(Object::Null),
+                None if self.is_error(&index) => index,
+                None => Object::Error(ErrorKind::UnhashableKey(index.to_string()).into()),
             },
-            _ => Object::Error(format!("unknown operator: {} {}", left, index)),
+            _ => Object::Error(
+                ErrorKind::TypeMismatch(format!("{} cannot be indexed, only arrays, strings and objects can", left)).into(),
+            ),
         }
     }
 
-    fn eval_array_index_expr(&mut self, array: Vec<Object>, index: f64) -> Object {
-        let max = array.len() as f64;
+    /// ## eval_slice_expr
+    /// Evaluates `seq[start:end:step]`. It works like slicing in Python: a missing
+    /// `start` or `end` means "from the beginning" and "to the end" (the other way
+    /// round when `step` is negative), negative bounds count from the end, and bounds
+    /// past either end are clamped. Strings are sliced by character.
+    /// # Arguments
+    /// * `seq` - The array or string to slice.
+    /// * `start` - The first index to take.
+    /// * `end` - The index to stop before.
+    /// * `step` - How far to move between indexes, `1` if left out.
+    /// # Returns
+    /// `Object` - A new array or string, or an error if a bound is not a whole number.
+    fn eval_slice_expr(&mut self, seq: Object, start: Option<Object>, end: Option<Object>, step: Option<Object>) -> Object {
+        if self.is_error(&seq) {
+            return seq;
+        }
+        for bound in [&start, &end, &step].into_iter().flatten() {
+            if self.is_error(bound) {
+                return bound.clone();
+            }
+        }
+        let whole = |bound: &Option<Object>| match bound {
+            Some(b) => b.as_i64().map(Some),
+            None => Some(None),
+        };
+        let (from, to, by) = match (whole(&start), whole(&end), whole(&step)) {
+            (Some(from), Some(to), Some(by)) => (from, to, by.unwrap_or(1)),
+            _ => {
+                let show = |bound: &Option<Object>| bound.as_ref().map_or(String::new(), |b| b.to_string());
+                return Object::Error(ErrorKind::TypeMismatch(format!(
+                    "slice bounds must be whole numbers, got [{}:{}:{}]", show(&start), show(&end), show(&step)
+                )).into());
+            }
+        };
+        if by == 0 {
+            return Object::Error(ErrorKind::Arithmetic(String::from("a slice cannot step by 0")).into());
+        }
+
+        match seq {
+            Object::Array(ref arr) => {
+                let arr = arr.borrow();
+                Object::array(slice_indices(arr.len(), from, to, by).into_iter().map(|i| arr[i].clone()).collect())
+            }
+            Object::String(ref s) => {
+                let chars = s.chars().collect::<Vec<_>>();
+                Object::String(slice_indices(chars.len(), from, to, by).into_iter().map(|i| chars[i]).collect())
+            }
+            o => Object::Error(ErrorKind::TypeMismatch(format!("only arrays and strings can be sliced, got {}", o)).into()),
+        }
+    }
+
+    fn eval_array_index_expr(&mut self, array: &[Object], index: i64) -> Object {
+        let max = array.len() as i64;
         if index > max {
             return Object::Null;
         }
 
-        if index < 0.0 {
-            match array.get((array.len() as f64 + index) as usize) {
+        if index < 0 {
+            match array.get((max + index) as usize) {
                 Some(o) => return o.clone(),
                 None => return Object::Null,
             }
@@ -530,28 +1135,46 @@ impl Eval {
         }
     }
 
-    fn apply_function(&mut self, function: Expr, args: Vec<Object>) -> Object {
+    fn apply_function(&mut self, function: Expr, args: Vec<Object>, call_site: &Span) -> Object {
+        let name = match function.kind {
+            ExprKind::Ident(Ident(ref name)) => name.clone(),
+            _ => String::from("<anonymous pawction>"),
+        };
         let (params, body, store) = match self.eval_expr(function) {
             Some(Object::Fn(params, body, store)) => (params, body, store),
             Some(Object::Inbuilt(func)) => return func(args),
-            Some(o) => return Object::Error(format!("🙀 Cat-tastrophe alert! The function '{}' is nowhere to be found, like a clever cat hiding in plain sight", o)),
+            Some(Object::Error(e)) => return Object::Error(e),
+            Some(o) => return Object::Error(ErrorKind::NotCallable(o.to_string()).into()),
             None => return Object::Null,
         };
 
         if params.len() != args.len() {
-            return Object::Error(format!(
-                "expected arguments: {}\ngiven arguments: {}",
-                params.len(),
-                args.len()
-            ));
+            let declared = params.iter().map(|Ident(p)| p.as_str()).collect::<Vec<_>>().join(", ");
+            return Object::Error(
+                Error::from(ErrorKind::Arity { expected: params.len(), found: args.len() })
+                    .with_note(format!("{} is declared as pawction({})", name, declared)),
+            );
         };
 
         let current_store = Rc::clone(&self.store);
         let extended_store = self.extended_function_store(params, store, args);
         self.store = Rc::new(RefCell::new(extended_store));
+        self.call_stack.push(Frame {
+            name,
+            call_site: call_site.clone(),
+        });
         let evaluated = self.eval_block_statement(body);
+        let result = match self.unwrap_return_value(evaluated) {
+            // The error is leaving the call it was raised in, record how we got here.
+            Object::Error(mut e) if e.backtrace.is_empty() => {
+                e.backtrace = self.call_stack.iter().rev().cloned().collect();
+                Object::Error(e)
+            }
+            o => o,
+        };
+        self.call_stack.pop();
         self.store = current_store;
-        self.unwrap_return_value(evaluated)
+        result
     }
 
     fn extended_function_store(
@@ -573,7 +1196,7 @@ impl Eval {
     fn extend_global_store(&mut self, lib: String) -> Option<Object> {
         let lib_store = match load_meow(lib.clone()) {
             Some(e) => e,
-            None => return Some(Object::Error(format!("Could not load lib: {}", lib))),
+            None => return Some(Object::Error(ErrorKind::UnknownLibrary(lib).into())),
         };
         let mut new_store = Store::new_enclosed(self.store.clone());
         for (k, v) in lib_store {
@@ -591,15 +1214,20 @@ impl Eval {
         }
     }
 
+    /// ## eval_ident
+    /// Looks up a variable. Variables shadow the builtins, so a script can name
+    /// its own things `error` or `same` and still use the builtins everywhere else.
     fn eval_ident(&mut self, ident: Ident) -> Object {
         let Ident(i) = ident;
-        let builtins =  new_globals();
-        if builtins.contains_key(&i) {
-            return builtins.get(&i).unwrap().clone();
-        };
-        match self.store.borrow_mut().get(&i) {
-            Some(i) => i,
-            None => Object::Error(format!("😾 Meow-sterious! The identifier '{}' seems to be playing a game of hide-and-seek", i)),
+        if let Some(o) = self.store.borrow_mut().get(&i) {
+            return o;
+        }
+        match new_globals().remove(&i) {
+            Some(builtin) => builtin,
+            None => Object::Error(
+                Error::from(ErrorKind::UnknownIdentifier(i.clone()))
+                    .with_help(format!("declare it first with `scratch {} = ...;`", i)),
+            ),
         }
     }
 
@@ -614,13 +1242,15 @@ impl Eval {
     fn eval_literal(&mut self, lit: Literal) -> Object {
         match lit {
             Literal::String(s) => Object::String(s),
+            Literal::Int(i) => Object::Int(i),
+            Literal::BigInt(i) => Object::BigInt(i),
             Literal::Number(i) => Object::Number(i),
             Literal::Boolean(b) => Object::Bool(b),
-            Literal::Array(a) => Object::Array(
-                a.iter()
-                    .map(|e| self.eval_expr(e.clone()).unwrap_or(Object::Null))
-                    .collect::<Vec<_>>(),
-            ),
+            // An element that failed fails the whole array, so it can be caught.
+            Literal::Array(a) => match self.eval_exprs(a) {
+                Ok(elements) => Object::array(elements),
+                Err(e) => *e,
+            },
             Literal::Object(h) => self.eval_object_literal(h),
         }
     }
@@ -637,13 +1267,17 @@ impl Eval {
     /// assert_eq!(result, Object::Object(vec![(Ident::new("a"), Object::Number(1)), (Ident::new("b"), Object::Number(2))]));
     /// ```
     fn eval_object_literal(&mut self, h: Vec<(Expr, Expr)>) -> Object {
-        let mut hash = HashMap::new();
+        let mut hash = IndexMap::new();
 
         for (k, v) in h {
             let key = self.eval_expr(k).unwrap_or(Object::Null);
             if self.is_error(&key) {
                 return key;
             }
+            let key = match key.as_key() {
+                Some(key) => key,
+                None => return Object::Error(ErrorKind::UnhashableKey(key.to_string()).into()),
+            };
 
             let val = self.eval_expr(v).unwrap_or(Object::Null);
             if self.is_error(&val) {
@@ -652,6 +1286,199 @@ impl Eval {
 
             hash.insert(key, val);
         }
-        Object::Object(hash)
+        Object::map(hash)
+    }
+}
+
+/// The part of a sequence of length `len` that the range `start..end`, or `start..=end`
+/// when `inclusive`, covers, as indexes that can be sliced with. Negative bounds count
+/// from the end like they do when indexing, so `seq[0..=-1]` is all of `seq`, and bounds
+/// past either end are clamped, so the slice may be empty.
+fn slice_bounds(len: usize, start: i64, end: i64, inclusive: bool) -> (usize, usize) {
+    let resolve = |i: i64| if i < 0 { i + len as i64 } else { i };
+    let end = if inclusive { resolve(end).saturating_add(1) } else { resolve(end) };
+    let (from, to) = (resolve(start).clamp(0, len as i64) as usize, end.clamp(0, len as i64) as usize);
+    (from, to.max(from))
+}
+
+/// The indexes `seq[start:end:step]` picks out of a sequence of length `len`, in order.
+/// See `Eval::eval_slice_expr` for how missing and out of range bounds are treated.
+/// `step` must not be 0.
+fn slice_indices(len: usize, start: Option<i64>, end: Option<i64>, step: i64) -> Vec<usize> {
+    if step > 0 {
+        let (from, to) = slice_bounds(len, start.unwrap_or(0), end.unwrap_or(len as i64), false);
+        return (from..to).step_by(step as usize).collect();
+    }
+    // Going backwards the bounds are clamped to -1, one before the first element,
+    // so that `seq[::-1]` can stop after index 0.
+    let len = len as i64;
+    let clamp = |i: i64| {
+        let i = if i < 0 { i + len } else { i };
+        i.clamp(-1, len - 1)
+    };
+    let from = start.map_or(len - 1, clamp);
+    let to = end.map_or(-1, clamp);
+    let mut indices = vec![];
+    let mut i = from;
+    while i > to {
+        indices.push(i as usize);
+        match i.checked_add(step) {
+            Some(next) => i = next,
+            None => break,
+        }
+    }
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexer::Lexer, parser::Parser};
+
+    fn eval(input: &str) -> Object {
+        let mut parser = Parser::new(Lexer::new(input.to_string()));
+        let program = parser.parse_program();
+        assert!(parser.errors.is_empty(), "{} failed to parse: {:?}", input, parser.errors);
+        let mut eval = Eval::new(Rc::new(RefCell::new(Store::new())));
+        eval.eval(program).unwrap_or(Object::Null)
+    }
+
+    #[test]
+    fn logical_operators_short_circuit() {
+        // `nope` is never declared, evaluating it would be an error.
+        assert_eq!(eval("clawful && nope"), Object::Bool(false));
+        assert_eq!(eval("purrfect || nope"), Object::Bool(true));
+        assert!(matches!(eval("purrfect && nope"), Object::Error(_)));
+        assert!(matches!(eval("clawful || nope"), Object::Error(_)));
+    }
+
+    #[test]
+    fn logical_operators_give_back_an_operand() {
+        assert_eq!(eval("1 && 2"), Object::Int(2));
+        assert_eq!(eval("clawful || 5"), Object::Int(5));
+        assert_eq!(eval("0 || 5"), Object::Int(0));
+    }
+
+    #[test]
+    fn operators_evaluate_in_precedence_order() {
+        assert_eq!(eval("2 + 3 * 4"), Object::Int(14));
+        assert_eq!(eval("6 & 3 == 2"), Object::Bool(true));
+        assert_eq!(eval("1 << 1 + 2"), Object::Int(8));
+        assert_eq!(eval("-8 >> 1"), Object::Int(-4));
+        assert_eq!(eval("1 < 2 && 3 <= 3 || nope"), Object::Bool(true));
+    }
+
+    #[test]
+    fn errors_can_be_caught() {
+        assert_eq!(eval("attempurr { purrhaps (nope) { 1; } } catch (e) { e's kind }"), Object::String(String::from("UnknownIdentifier")));
+        assert_eq!(eval("attempurr { hairball \"oops\"; } catch (e) { e's kind }"), Object::String(String::from("Hairball")));
+        assert_eq!(eval("attempurr { hairball error(\"oops\", 7); } catch (e) { e's payload }"), Object::Int(7));
+        assert_eq!(eval("attempurr { [1] + 1; } catch (e) { e's kind }"), Object::String(String::from("UnknownOperator")));
+    }
+
+    #[test]
+    fn variables_shadow_builtins() {
+        assert_eq!(eval("attempurr { hairball \"oops\"; } catch (error) { error's message }"), Object::String(String::from("oops")));
+        assert_eq!(eval("scratch error = 5; error"), Object::Int(5));
+        assert_eq!(eval("scratch same = 1; same"), Object::Int(1));
+        assert_eq!(eval("same([1], [1])"), Object::Bool(false));
+    }
+
+    #[test]
+    fn nothing_runs_after_a_failed_element_or_argument() {
+        let bumps = "scratch n = 0; scratch bump = pawction() { amew n = n + 1; tail n; };";
+        assert_eq!(eval(&format!("{} attempurr {{ [nope, bump()]; }} catch {{}} n", bumps)), Object::Int(0));
+        assert_eq!(eval(&format!("{} attempurr {{ meow(nope, bump()); }} catch {{}} n", bumps)), Object::Int(0));
+    }
+
+    #[test]
+    fn compound_assignment_evaluates_the_index_once() {
+        let setup = "scratch calls = 0; scratch next = pawction() { calls++; tail calls; }; scratch a = [0, 10, 20];";
+        assert_eq!(eval(&format!("{} a[next()] += 5; calls", setup)), Object::Int(1));
+        assert_eq!(eval(&format!("{} a[next()] += 5; a[1]", setup)), Object::Int(15));
+        assert_eq!(eval(&format!("{} a[next()]++; a", setup)), Object::array(vec![Object::Int(0), Object::Int(11), Object::Int(20)]));
+    }
+
+    #[test]
+    fn inclusive_ranges_slice_up_to_a_negative_end() {
+        let ints = |v: &[i64]| Object::array(v.iter().copied().map(Object::Int).collect());
+        assert_eq!(eval("[1, 2, 3][0..=-1]"), ints(&[1, 2, 3]));
+        assert_eq!(eval("[1, 2, 3][0..-1]"), ints(&[1, 2]));
+        assert_eq!(eval("[1, 2, 3][-2..=-2]"), ints(&[2]));
+        assert_eq!(eval("[1, 2, 3][0..=-9]"), ints(&[]));
+        assert_eq!(eval("\"meow\"[1..=-1]"), Object::String(String::from("eow")));
+    }
+
+    #[test]
+    fn only_containers_can_be_indexed() {
+        for input in ["(0..5)[1]", "5[0]", "purrfect[0]"] {
+            assert!(matches!(eval(input), Object::Error(Error { kind: ErrorKind::TypeMismatch(_), .. })), "{}", input);
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn loops_give_the_value_they_are_hissed_with() {
+        assert_eq!(eval("scratch n = 0; furrever { n++; purrhaps (n == 3) { hiss n * 10; } }"), Object::Int(30));
+        assert_eq!(eval("fur (x ~ [1, 2]) { hiss; }"), Object::Null);
+        assert_eq!(eval("fur (x ~ [1, 2]) { }"), Object::Null);
+        assert_eq!(eval("purrsist (purrfect) { hiss }"), Object::Null);
+    }
+
+    #[test]
+    fn labeled_jumps_reach_outer_loops() {
+        let found = "'outer: fur (i ~ 0..5) { fur (j ~ 0..5) { purrhaps (i * j == 6) { hiss 'outer [i, j]; } } }";
+        assert_eq!(eval(found), Object::array(vec![Object::Int(2), Object::Int(3)]));
+        let skipped = "scratch n = 0; 'a: fur (i ~ 0..3) { fur (j ~ 0..3) { continue 'a; } amew n = 99; } n";
+        assert_eq!(eval(skipped), Object::Int(0));
+        let inner = "scratch n = 0; 'a: fur (i ~ 0..3) { 'b: fur (j ~ 0..3) { hiss 'b; } n++; } n";
+        assert_eq!(eval(inner), Object::Int(3));
+    }
+
+    #[test]
+    fn equal_keys_find_the_same_entry() {
+        assert_eq!(eval("scratch o = {1: \"a\"}; o[1.0]"), Object::String(String::from("a")));
+        assert_eq!(eval("scratch o = {[1, [2]]: \"a\"}; o[[1, [2]]]"), Object::String(String::from("a")));
+        assert_eq!(eval("scratch o = {purrfect: 1, \"purrfect\": 2}; o[purrfect]"), Object::Int(1));
+        assert_eq!(eval("scratch k = [1]; scratch o = {k: 1}; amew k[0] = 2; [[1] ~ o, [2] ~ o]"), Object::array(vec![Object::Bool(true), Object::Bool(false)]));
+    }
+
+    #[test]
+    fn arrays_that_hold_themselves_are_not_keys() {
+        for input in [
+            "scratch a = [1]; amew a[0] = a; {a: 1}",
+            "scratch a = [1]; amew a[0] = a; a ~ {}",
+            "scratch a = [1]; amew a[0] = a; scratch o = {}; amew o[a] = 1;",
+        ] {
+            assert!(matches!(eval(input), Object::Error(Error { kind: ErrorKind::UnhashableKey(_), .. })), "{}", input);
+        }
+        assert!(matches!(eval("{{}: 1}"), Object::Error(Error { kind: ErrorKind::UnhashableKey(_), .. })));
+    }
+
+    #[test]
+    fn slices_take_negative_and_stepped_bounds() {
+        let ints = |v: &[i64]| Object::array(v.iter().copied().map(Object::Int).collect());
+        let nums = "scratch nums = [0, 1, 2, 3, 4, 5];";
+        assert_eq!(eval(&format!("{} nums[1:4]", nums)), ints(&[1, 2, 3]));
+        assert_eq!(eval(&format!("{} nums[-2:]", nums)), ints(&[4, 5]));
+        assert_eq!(eval(&format!("{} nums[:-4]", nums)), ints(&[0, 1]));
+        assert_eq!(eval(&format!("{} nums[::2]", nums)), ints(&[0, 2, 4]));
+        assert_eq!(eval(&format!("{} nums[::-2]", nums)), ints(&[5, 3, 1]));
+        assert_eq!(eval(&format!("{} nums[4:1:-1]", nums)), ints(&[4, 3, 2]));
+        assert_eq!(eval(&format!("{} nums[-100:100]", nums)), ints(&[0, 1, 2, 3, 4, 5]));
+        assert_eq!(eval(&format!("{} nums[4:1]", nums)), ints(&[]));
+        assert_eq!(eval("\"meow🐱\"[::-1]"), Object::String(String::from("🐱woem")));
+        assert_eq!(eval("\"meow🐱\"[-1]"), Object::String(String::from("🐱")));
+        assert!(matches!(eval("[1, 2][::0]"), Object::Error(_)));
+    }
+
+    #[test]
+    fn arity_errors_note_the_declaration() {
+        match eval("scratch greet = pawction(name, mood) { tail name; }; greet(1);") {
+            Object::Error(e) => {
+                assert_eq!(e.kind, ErrorKind::Arity { expected: 2, found: 1 });
+                assert_eq!(e.notes, vec![String::from("greet is declared as pawction(name, mood)")]);
+            }
+            o => panic!("expected an arity error, got {}", o),
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use super::store::Store;
-use crate::ast::{BlockStatement, Ident};
+use crate::{ast::{BlockStatement, Ident}, error::Error};
+use indexmap::IndexMap;
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive};
 use std::{
     cell::RefCell,
-    collections::HashMap,
     fmt,
     hash::{Hash, Hasher},
+    ops::RangeInclusive,
     rc::Rc,
 };
 
@@ -12,26 +15,52 @@ pub type InbuiltFunction = fn(Vec<Object>) -> Object;
 
 #[derive(Clone, Debug)]
 pub enum Object {
+    Int(i64),
+    /// An integer too big for `Int`. Arithmetic promotes to it on overflow,
+    /// and results that fit in an `Int` again are turned back into one.
+    BigInt(BigInt),
     Number(f64),
     String(String),
     Bool(bool),
     Null,
     Return(Box<Object>),
-    Error(String),
+    Error(Error),
     Fn(Vec<Ident>, BlockStatement, Rc<RefCell<Store>>),
     Inbuilt(InbuiltFunction),
-    Array(Vec<Object>),
-    Object(HashMap<Object, Object>),
+    /// Arrays and objects are shared: copying one, passing it to a pawction or
+    /// reading it out of another container hands out the same value, so changes
+    /// made through any of them are seen by all. `==` compares contents,
+    /// see `is_same` to compare identity.
+    Array(Rc<RefCell<Vec<Object>>>),
+    /// Keys are kept in the order they were first added, which is the order
+    /// objects are displayed and iterated in.
+    Object(Rc<RefCell<IndexMap<Object, Object>>>),
+    /// The integers from `start` up to `end`, made by `start..end` or, including
+    /// `end` itself, by `start..=end`. The integers are only produced when they are needed.
+    Range(i64, i64, bool),
     Typeof(Box<Object>),
     Loop(Box<BlockStatement>),
-    Break,
-    Continue
+    /// Raised by `hiss`, with the label of the loop to leave, if any,
+    /// and the value that loop evaluates to.
+    Break(Option<String>, Box<Object>),
+    /// Raised by `continue`, with the label of the loop to continue, if any.
+    Continue(Option<String>)
 }
 
 impl PartialEq for Object {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Object::Number(a), Object::Number(b)) => a == b,
+            (Object::Int(a), Object::Int(b)) => a == b,
+            (Object::BigInt(a), Object::BigInt(b)) => a == b,
+            (Object::BigInt(a), Object::Number(b)) | (Object::Number(b), Object::BigInt(a)) => {
+                whole_big_number(*b).as_ref() == Some(a)
+            }
+            // NaN equals itself here so it can be found again as an object key,
+            // `==` in scripts compares floats itself and keeps NaN != NaN.
+            (Object::Number(a), Object::Number(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Object::Int(a), Object::Number(b)) | (Object::Number(b), Object::Int(a)) => {
+                whole_number(*b) == Some(*a)
+            }
             (Object::String(a), Object::String(b)) => a == b,
             (Object::Bool(a), Object::Bool(b)) => a == b,
             (Object::Null, Object::Null) => true,
@@ -39,8 +68,13 @@ impl PartialEq for Object {
             (Object::Error(a), Object::Error(b)) => a == b,
             (Object::Fn(a, b, c), Object::Fn(d, e, f)) => a == d && b == e && c == f,
             (Object::Inbuilt(a), Object::Inbuilt(b)) => a == b,
-            (Object::Array(a), Object::Array(b)) => a == b,
-            (Object::Object(a), Object::Object(b)) => a == b,
+            (Object::Array(a), Object::Array(b)) => {
+                Rc::ptr_eq(a, b) || eq_shared(Rc::as_ptr(a) as *const (), Rc::as_ptr(b) as *const (), || *a.borrow() == *b.borrow())
+            }
+            (Object::Object(a), Object::Object(b)) => {
+                Rc::ptr_eq(a, b) || eq_shared(Rc::as_ptr(a) as *const (), Rc::as_ptr(b) as *const (), || *a.borrow() == *b.borrow())
+            }
+            (Object::Range(a, b, c), Object::Range(d, e, f)) => Object::ints(*a, *b, *c) == Object::ints(*d, *e, *f),
             (Object::Typeof(a), Object::Typeof(b)) => a == b,
             _ => false,
         }
@@ -49,9 +83,151 @@ impl PartialEq for Object {
 
 impl Eq for Object {}
 
+thread_local! {
+    /// The pairs of arrays and objects currently being compared, outermost first.
+    static COMPARING: RefCell<Vec<(*const (), *const ())>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Compares two arrays or objects with `body`, unless the same pair is already
+/// being compared further up, as with two arrays that each hold themselves.
+/// Then they are taken to be equal, since nothing found so far tells them apart.
+fn eq_shared(a: *const (), b: *const (), body: impl FnOnce() -> bool) -> bool {
+    if COMPARING.with(|c| c.borrow().contains(&(a, b))) {
+        return true;
+    }
+    COMPARING.with(|c| c.borrow_mut().push((a, b)));
+    let res = body();
+    COMPARING.with(|c| c.borrow_mut().pop());
+    res
+}
+
+/// The integer a float is exactly equal to, if there is one.
+fn whole_number(n: f64) -> Option<i64> {
+    if n.fract() == 0.0 && n >= i64::MIN as f64 && n < i64::MAX as f64 {
+        Some(n as i64)
+    } else {
+        None
+    }
+}
+
+/// The big integer a float is exactly equal to, if it is a whole number.
+fn whole_big_number(n: f64) -> Option<BigInt> {
+    if n.fract() == 0.0 {
+        BigInt::from_f64(n)
+    } else {
+        None
+    }
+}
+
+impl Object {
+    /// A new array holding `items`.
+    pub fn array(items: Vec<Object>) -> Object {
+        Object::Array(Rc::new(RefCell::new(items)))
+    }
+
+    /// A new object holding the entries of `hash`, in their current order.
+    pub fn map(hash: IndexMap<Object, Object>) -> Object {
+        Object::Object(Rc::new(RefCell::new(hash)))
+    }
+
+    /// Whether `self` and `other` are the very same value. Arrays and objects
+    /// are only the same as themselves, even if another one has equal contents,
+    /// while every other value is the same as anything equal to it.
+    pub fn is_same(&self, other: &Object) -> bool {
+        match (self, other) {
+            (Object::Array(a), Object::Array(b)) => Rc::ptr_eq(a, b),
+            (Object::Object(a), Object::Object(b)) => Rc::ptr_eq(a, b),
+            _ => self == other,
+        }
+    }
+
+    /// ## as_key
+    /// The value to store when `self` is used as an object key.
+    /// Numbers, strings, booleans and null are keys as they are. Arrays of them
+    /// are copied, so changing the original array later cannot change a key
+    /// that is already in an object.
+    /// # Returns
+    /// `None` for values that cannot be keys, such as pawctions, objects
+    /// and arrays that hold themselves.
+    pub fn as_key(&self) -> Option<Object> {
+        self.key_of(&mut vec![])
+    }
+
+    /// `as_key`, with the arrays that are being copied further up in `copying`.
+    fn key_of(&self, copying: &mut Vec<*const ()>) -> Option<Object> {
+        match self {
+            Object::Int(_) | Object::BigInt(_) | Object::Number(_) | Object::String(_) | Object::Bool(_) | Object::Null => {
+                Some(self.clone())
+            }
+            Object::Array(a) => {
+                let ptr = Rc::as_ptr(a) as *const ();
+                if copying.contains(&ptr) {
+                    return None;
+                }
+                copying.push(ptr);
+                let key = a.borrow().iter().map(|o| o.key_of(copying)).collect::<Option<Vec<_>>>();
+                copying.pop();
+                key.map(Object::array)
+            }
+            _ => None,
+        }
+    }
+
+    /// The most bits a `<<` or `pow` may produce. Anything bigger would need more memory than there is.
+    pub const MAX_INTEGER_BITS: u64 = 1 << 24;
+
+    /// The integers in the range `start..end`, or `start..=end` when `inclusive`.
+    pub fn ints(start: i64, end: i64, inclusive: bool) -> RangeInclusive<i64> {
+        match (inclusive, end.checked_sub(1)) {
+            (true, _) => start..=end,
+            (false, Some(last)) => start..=last,
+            // `end` is `i64::MIN` and nothing comes before it.
+            (false, None) => 0..=end,
+        }
+    }
+
+    /// The integer `n`, as an `Int` when it fits in one and as a `BigInt` otherwise.
+    pub fn integer(n: BigInt) -> Object {
+        match i64::try_from(&n) {
+            Ok(i) => Object::Int(i),
+            Err(_) => Object::BigInt(n),
+        }
+    }
+
+    /// The value of an integer or float as a float.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Object::Int(i) => Some(i as f64),
+            Object::BigInt(ref b) => b.to_f64(),
+            Object::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// The value of an `Int` or `BigInt` as a big integer.
+    pub fn as_big_int(&self) -> Option<BigInt> {
+        match *self {
+            Object::Int(i) => Some(BigInt::from(i)),
+            Object::BigInt(ref b) => Some(b.clone()),
+            _ => None,
+        }
+    }
+
+    /// The value of an integer, or of a float that holds a whole number, as an integer.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Object::Int(i) => Some(i),
+            Object::Number(n) => whole_number(n),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for Object {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            Object::Int(ref value) => write!(f, "{}", value),
+            Object::BigInt(ref value) => write!(f, "{}", value),
             Object::Number(ref value) => write!(f, "{}", value),
             Object::String(ref value) => write!(f, "{}", value),
             Object::Bool(ref value) => write!(f, "{}", value),
@@ -70,9 +246,9 @@ impl fmt::Display for Object {
                 write!(f, "fn({}) {{ ... }}", result)
             }
             Object::Inbuilt(_) => write!(f, "[inbuilt fn]"),
-            Object::Array(ref val) => {
+            Object::Array(ref val) => display_shared(f, Rc::as_ptr(val) as *const (), "[...]", |f| {
                 let mut result = String::new();
-                for (i, obj) in val.iter().enumerate() {
+                for (i, obj) in val.borrow().iter().enumerate() {
                     if i < 1 {
                         result.push_str(&format!("{}", obj));
                     } else {
@@ -80,10 +256,10 @@ impl fmt::Display for Object {
                     }
                 }
                 write!(f, "[{}]", result)
-            }
-            Object::Object(ref hash) => {
+            }),
+            Object::Object(ref hash) => display_shared(f, Rc::as_ptr(hash) as *const (), "{...}", |f| {
                 let mut res = String::new();
-                for (i, (k, v)) in hash.iter().enumerate() {
+                for (i, (k, v)) in hash.borrow().iter().enumerate() {
                     if i < 1 {
                         res.push_str(&format!("{}: {}", k, v));
                     } else {
@@ -92,20 +268,64 @@ impl fmt::Display for Object {
                 }
 
                 write!(f, "{{{}}}", res)
-            }
+            }),
+            Object::Range(start, end, inclusive) => write!(f, "{}{}{}", start, if inclusive { "..=" } else { ".." }, end),
             Object::Typeof(ref obj) => write!(f, "typeof({})", obj),
             Object::Loop(ref _block) => write!(f, "loop {{ ... }}"),
-            Object::Break => write!(f, "break"),
-            Object::Continue => write!(f, "continue"),
+            Object::Break(..) => write!(f, "break"),
+            Object::Continue(_) => write!(f, "continue"),
         }
     }
 }
 
+thread_local! {
+    /// The arrays and objects currently being displayed, outermost first.
+    static DISPLAYING: RefCell<Vec<*const ()>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Displays an array or object with `body`, unless it is already being displayed
+/// further up, as in `amew a[0] = a;`. Then `placeholder` is written instead of
+/// recursing forever.
+fn display_shared(
+    f: &mut fmt::Formatter,
+    ptr: *const (),
+    placeholder: &str,
+    body: impl FnOnce(&mut fmt::Formatter) -> fmt::Result,
+) -> fmt::Result {
+    if DISPLAYING.with(|d| d.borrow().contains(&ptr)) {
+        return write!(f, "{}", placeholder);
+    }
+    DISPLAYING.with(|d| d.borrow_mut().push(ptr));
+    let res = body(f);
+    DISPLAYING.with(|d| d.borrow_mut().pop());
+    res
+}
+
 impl Hash for Object {
     fn hash<H: Hasher>(&self, state: &mut H) {
         match *self {
+            Object::Int(ref i) => i.hash(state),
+            // A whole float equals the matching integer, so it has to hash like one.
+            Object::BigInt(ref b) => b.hash(state),
+            // `-0.0` is whole and hashes like `0`, and every NaN hashes alike.
+            Object::Number(n) => match (whole_number(n), whole_big_number(n)) {
+                (Some(i), _) => i.hash(state),
+                (None, Some(b)) => b.hash(state),
+                (None, None) if n.is_nan() => f64::NAN.to_bits().hash(state),
+                (None, None) => n.to_bits().hash(state),
+            },
             Object::Bool(ref b) => b.hash(state),
             Object::String(ref s) => s.hash(state),
+            Object::Null => 0u8.hash(state),
+            // Keys are copies made by `as_key`, which never hold themselves.
+            Object::Array(ref a) => {
+                let a = a.borrow();
+                a.len().hash(state);
+                for o in a.iter() {
+                    o.hash(state);
+                }
+            }
+            // Everything else is turned away by `as_key` before it gets near a hash map.
             _ => "".hash(state),
         }
     }
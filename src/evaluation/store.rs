@@ -5,6 +5,8 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 pub struct Store {
     pub store: HashMap<String, Object>,
     pub outer: Option<Rc<RefCell<Store>>>,
+    /// Doc comments of the bindings declared in this scope.
+    pub docs: HashMap<String, String>,
 }
 
 impl Store {
@@ -12,17 +14,23 @@ impl Store {
         Self {
             store: HashMap::new(),
             outer: None,
+            docs: HashMap::new(),
         }
     }
 
     pub fn from(store: HashMap<String, Object>) -> Self {
-        Self { store, outer: None }
+        Self {
+            store,
+            outer: None,
+            docs: HashMap::new(),
+        }
     }
 
     pub fn new_enclosed(outer: Rc<RefCell<Store>>) -> Self {
         Self {
             store: HashMap::new(),
             outer: Some(outer),
+            docs: HashMap::new(),
         }
     }
 
@@ -39,6 +47,19 @@ impl Store {
         }
     }
 
+    /// Records the doc comment of a binding in this scope.
+    pub fn document(&mut self, name: String, doc: String) {
+        self.docs.insert(name, doc);
+    }
+
+    /// Looks up the doc comment of `name`, searching outer scopes like `get`.
+    pub fn doc(&self, name: &str) -> Option<String> {
+        match self.docs.get(name) {
+            Some(doc) => Some(doc.clone()),
+            None => self.outer.as_ref().and_then(|o| o.borrow().doc(name)),
+        }
+    }
+
     pub fn set(&mut self, name: String, val: Object) -> Option<Object> {
         self.store.insert(name, val)
     }
@@ -1,5 +1,9 @@
-use crate::ast::token::Token;
-use std::collections::HashMap;
+use crate::{
+    ast::token::{Span, TemplatePart, Token},
+    error::{Error, ErrorKind},
+};
+use num_bigint::BigInt;
+use std::{collections::HashMap, rc::Rc};
 
 lazy_static::lazy_static! {
     static ref KEYWORDS: HashMap<&'static str, Token> = {
@@ -15,8 +19,14 @@ lazy_static::lazy_static! {
         keywords.insert("clawful", Token::Boolean(false));
         keywords.insert("furreal", Token::Typeof);
         keywords.insert("furrever", Token::Loop);
+        keywords.insert("purrsist", Token::While);
+        keywords.insert("fur", Token::For);
         keywords.insert("hiss", Token::Break);
         keywords.insert("continue", Token::Continue);
+        keywords.insert("attempurr", Token::Try);
+        keywords.insert("catch", Token::Catch);
+        keywords.insert("finurrly", Token::Finally);
+        keywords.insert("hairball", Token::Throw);
 
         // Kaomojis
         /* 
@@ -36,39 +46,93 @@ lazy_static::lazy_static! {
 }
 
 fn is_letter(c: char) -> bool {
-    c.is_ascii_alphabetic() || c == '_'
+    c.is_alphabetic() || c == '_'
+}
+
+/// The smallest indentation of the non-blank lines after the first line of `text`.
+fn common_indent(text: &str) -> usize {
+    text.lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().take_while(|c| *c == ' ' || *c == '\t').count())
+        .min()
+        .unwrap_or(0)
 }
 
 pub fn find_indentifier(ident: &str) -> Option<&Token> {
     KEYWORDS.get(ident)
 }
 
+/// ## Lexer
+/// Turns source text into tokens.
+/// `position` is the byte offset of `ch` and `read_position` the byte offset of the
+/// character after it, so slicing `input` between positions always lands on
+/// character boundaries and each step only decodes the character it moves onto.
 pub struct Lexer {
     input: String,
+    file: Rc<str>,
+    /// Byte offset of `input` within `file`, for lexers over a piece of a larger source.
+    base: usize,
     position: usize,
     read_position: usize,
     ch: char,
+    line: usize,
+    column: usize,
+    /// Byte offset, line and column where the current token started.
+    token_start: (usize, usize, usize),
+    /// Errors found while reading tokens, such as unterminated strings.
+    pub errors: Vec<Error>,
 }
 
 impl Lexer {
     pub fn new(input: String) -> Lexer {
+        Lexer::with_file(input, "<input>")
+    }
+
+    /// Creates a lexer whose spans report `file` as their source.
+    pub fn with_file(input: String, file: &str) -> Lexer {
+        let ch = input.chars().next().unwrap_or('\0');
         Lexer {
-            ch: input.chars().next().unwrap(),
+            read_position: if input.is_empty() { 0 } else { ch.len_utf8() },
+            ch,
             input,
+            file: Rc::from(file),
+            base: 0,
             position: 0,
-            read_position: 1,
+            line: 1,
+            column: 1,
+            token_start: (0, 1, 1),
+            errors: vec![],
         }
     }
 
+    /// Creates a lexer over a piece of a file that starts at `at`,
+    /// such as the expression inside a template string's `${...}`.
+    pub fn nested(input: String, at: &Span) -> Lexer {
+        let mut lexer = Lexer::with_file(input, "");
+        lexer.file = at.file.clone();
+        lexer.base = at.offset;
+        lexer.line = at.line;
+        lexer.column = at.column;
+        lexer.token_start = (0, at.line, at.column);
+        lexer
+    }
+
     pub fn read_char(&mut self) {
-        self.ch = if self.read_position >= self.input.len() {
-            '\0'
-        } else {
-            self.input.chars().nth(self.read_position).unwrap()
-        };
+        if self.position < self.input.len() {
+            if self.ch == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
 
         self.position = self.read_position;
-        self.read_position += 1;
+        self.ch = self.input[self.position..].chars().next().unwrap_or('\0');
+        if self.position < self.input.len() {
+            self.read_position += self.ch.len_utf8();
+        }
     }
 
     fn read_identifier(&mut self) -> String {
@@ -79,23 +143,295 @@ impl Lexer {
         self.input[pos..self.position].to_string()
     }
 
-    fn read_number(&mut self) -> f64 {
-        let pos: usize = self.position;
-        while self.ch.is_numeric() || self.ch == '.' {
+    /// ## read_number
+    /// Reads a number literal: a decimal with an optional fraction and exponent such as `1.5e-3`,
+    /// or a hexadecimal, binary or octal integer with a `0x`, `0b` or `0o` prefix.
+    /// Digits can be grouped with `_`, as in `1_000_000`.
+    /// Numbers without a fraction or exponent are integers, big integers when they do not fit in 64 bits.
+    /// Malformed numbers such as `1.2.3` or `0b102` are reported in `errors` and read as `0`.
+    fn read_number(&mut self) -> Token {
+        let (offset, line, column) = (self.position, self.line, self.column);
+        let radix = match (self.ch, self.peek_char()) {
+            ('0', 'x' | 'X') => 16,
+            ('0', 'b' | 'B') => 2,
+            ('0', 'o' | 'O') => 8,
+            _ => 10,
+        };
+        if radix != 10 {
+            self.read_char();
             self.read_char();
         }
-        self.input[pos..self.position].parse::<f64>().unwrap()
+
+        // Take everything that looks like part of the number, so a malformed
+        // literal is reported once as a whole instead of as a string of tokens.
+        loop {
+            let next = self.peek_char();
+            let exponent_sign = radix == 10 && matches!(self.ch, 'e' | 'E') && matches!(next, '+' | '-');
+            if self.ch.is_alphanumeric() || self.ch == '_' || (self.ch == '.' && next.is_ascii_digit()) {
+                self.read_char();
+                if exponent_sign {
+                    self.read_char();
+                }
+            } else {
+                break;
+            }
+        }
+
+        let text = self.input[offset..self.position].to_string();
+        let digits = if radix == 10 { &text[..] } else { &text[2..] };
+        let grouped = if radix == 10 {
+            digits
+                .split(['.', 'e', 'E', '+', '-'])
+                .all(|part| !part.starts_with('_') && !part.ends_with('_'))
+        } else {
+            !digits.ends_with('_')
+        };
+        let clean = digits.replace('_', "");
+        let integer = radix != 10 || !clean.contains(['.', 'e', 'E']);
+        let value = match (grouped, integer) {
+            (false, _) => None,
+            (true, true) => BigInt::parse_bytes(clean.as_bytes(), radix).map(|n| match i64::try_from(&n) {
+                Ok(n) => Token::Int(n),
+                Err(_) => Token::BigInt(n),
+            }),
+            (true, false) => clean.parse::<f64>().ok().map(Token::Number),
+        };
+
+        value.unwrap_or_else(|| {
+            let span = self.span_from(offset, line, column);
+            self.errors.push(Error::at(ErrorKind::MalformedNumber(text), span));
+            Token::Int(0)
+        })
+    }
+
+    /// Reads a `"` or `"""` string literal, with `ch` on its opening quote.
+    /// Raw strings keep every `\` as written.
+    fn read_quoted(&mut self, raw: bool) -> String {
+        if self.input[self.read_position..].starts_with("\"\"") {
+            self.read_char();
+            self.read_char();
+            self.read_multiline_string(raw)
+        } else {
+            self.read_string(raw)
+        }
     }
 
-    fn read_string(&mut self) -> String {
-        let pos: usize = self.position + 1;
+    /// Reports a string that ran into the end of the input.
+    fn unterminated_string(&mut self) {
+        let (offset, line, column) = self.token_start;
+        let span = self.span_from(offset, line, column);
+        self.errors.push(Error::at(ErrorKind::UnterminatedString, span));
+    }
+
+    /// ## read_string
+    /// Reads a string literal, replacing escape sequences with the characters they stand for.
+    /// Supports `\n`, `\t`, `\r`, `\0`, `\\`, `\"`, `\'` and `\u{1F431}` style Unicode escapes.
+    /// Unknown escapes and a missing closing quote are reported in `errors`.
+    /// # Arguments
+    /// * `raw` - Whether to keep escape sequences as written instead.
+    fn read_string(&mut self, raw: bool) -> String {
+        let mut s = String::new();
         loop {
             self.read_char();
-            if self.ch == '"' || self.ch == (0 as char) {
-                break;
+            match self.ch {
+                '"' => break,
+                '\0' if self.position >= self.input.len() => {
+                    self.unterminated_string();
+                    break;
+                }
+                '\\' if !raw => {
+                    if let Some(c) = self.read_escape() {
+                        s.push(c);
+                    }
+                }
+                c => s.push(c),
             }
         }
-        self.input[pos..self.position].to_string()
+        s
+    }
+
+    /// ## read_multiline_string
+    /// Reads a `"""` string, with `ch` on the last opening quote.
+    /// A line break right after the opening quotes is dropped, as is the line holding
+    /// the closing quotes when it is only whitespace. The indentation shared by the
+    /// remaining lines is stripped, so the string can be indented along with the code.
+    /// # Arguments
+    /// * `raw` - Whether to keep escape sequences as written.
+    fn read_multiline_string(&mut self, raw: bool) -> String {
+        let body = &self.input[self.read_position..];
+        let indent = body.find("\"\"\"").map_or(0, |end| common_indent(&body[..end]));
+
+        let mut s = String::new();
+        // Where the last line started in `s`, and whether it has been blank so far.
+        let mut last_line: Option<usize> = None;
+        let mut blank = true;
+        let mut at_line_start = false;
+
+        if self.peek_char() == '\r' {
+            self.read_char();
+        }
+        if self.peek_char() == '\n' {
+            self.read_char();
+            at_line_start = true;
+        }
+
+        loop {
+            self.read_char();
+            if at_line_start {
+                let mut skipped = 0;
+                while skipped < indent && (self.ch == ' ' || self.ch == '\t') {
+                    self.read_char();
+                    skipped += 1;
+                }
+                at_line_start = false;
+            }
+            match self.ch {
+                '"' if self.input[self.position..].starts_with("\"\"\"") => {
+                    self.read_char();
+                    self.read_char();
+                    break;
+                }
+                '\0' if self.position >= self.input.len() => {
+                    self.unterminated_string();
+                    return s;
+                }
+                '\n' => {
+                    last_line = Some(s.len());
+                    blank = true;
+                    at_line_start = true;
+                    s.push('\n');
+                }
+                '\\' if !raw => {
+                    blank = false;
+                    if let Some(c) = self.read_escape() {
+                        s.push(c);
+                    }
+                }
+                c => {
+                    blank &= c == ' ' || c == '\t' || c == '\r';
+                    s.push(c);
+                }
+            }
+        }
+
+        if let (Some(end), true) = (last_line, blank) {
+            s.truncate(end);
+            if s.ends_with('\r') {
+                s.pop();
+            }
+        }
+        s
+    }
+
+    /// Reads the escape sequence after a `\`, leaving `ch` on its last character.
+    fn read_escape(&mut self) -> Option<char> {
+        let (offset, line, column) = (self.position, self.line, self.column);
+        self.read_char();
+        let c = match self.ch {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' => '\\',
+            '"' => '"',
+            '\'' => '\'',
+            '`' => '`',
+            '$' => '$',
+            'u' if self.peek_char() == '{' => {
+                self.read_char();
+                let start = self.read_position;
+                while self.peek_char().is_ascii_hexdigit() {
+                    self.read_char();
+                }
+                let digits = self.input[start..self.read_position].to_string();
+                let c = if self.peek_char() == '}' {
+                    self.read_char();
+                    u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32)
+                } else {
+                    None
+                };
+                if c.is_none() {
+                    let span = self.span_from(offset, line, column);
+                    self.errors.push(Error::at(ErrorKind::InvalidEscape(format!("u{{{}}}", digits)), span));
+                }
+                return c;
+            }
+            '\0' if self.position >= self.input.len() => return None,
+            other => {
+                let mut span = self.span_from(offset, line, column);
+                span.len += other.len_utf8();
+                self.errors.push(Error::at(ErrorKind::InvalidEscape(other.escape_default().to_string()), span));
+                other
+            }
+        };
+        Some(c)
+    }
+
+    /// ## read_template
+    /// Reads a backtick template string, splitting it into literal text and the
+    /// source of each `${...}` expression. The expressions are parsed later by
+    /// the parser, which lexes each one with a `Lexer::nested` over its source.
+    fn read_template(&mut self) -> Vec<TemplatePart> {
+        let mut parts = vec![];
+        let mut text = String::new();
+        loop {
+            self.read_char();
+            match self.ch {
+                '`' => break,
+                '\0' if self.position >= self.input.len() => {
+                    self.unterminated_string();
+                    break;
+                }
+                '\\' => {
+                    if let Some(c) = self.read_escape() {
+                        text.push(c);
+                    }
+                }
+                '$' if self.peek_char() == '{' => {
+                    if !text.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+                    self.read_char();
+                    self.read_char();
+                    let (offset, line, column) = (self.position, self.line, self.column);
+                    self.skip_embedded_expr();
+                    parts.push(TemplatePart::Code {
+                        source: self.input[offset..self.position].to_string(),
+                        offset: self.base + offset,
+                        line,
+                        column,
+                    });
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() || parts.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+        parts
+    }
+
+    /// Moves to the `}` closing an embedded template expression,
+    /// skipping over nested braces and quoted strings.
+    fn skip_embedded_expr(&mut self) {
+        let mut depth = 0;
+        while self.position < self.input.len() {
+            match self.ch {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                quote @ ('"' | '`') => loop {
+                    self.read_char();
+                    if self.ch == '\\' {
+                        self.read_char();
+                    } else if self.ch == quote || self.position >= self.input.len() {
+                        break;
+                    }
+                },
+                _ => {}
+            }
+            self.read_char();
+        }
     }
 
     pub fn read_comment(&mut self) -> String {
@@ -109,21 +445,87 @@ impl Lexer {
         self.input[pos..self.position].to_string()
     }
 
+    /// Whether `ch` starts a `///` doc comment. Four or more slashes make a plain comment.
+    fn at_doc_comment(&self) -> bool {
+        let rest = &self.input[self.position..];
+        rest.starts_with("///") && !rest.starts_with("////")
+    }
+
+    /// Skips whitespace, `//` line comments and `/* */` block comments,
+    /// leaving `///` doc comments for `read_token`.
     fn skip_whitespace(&mut self) {
-        while self.ch == ' ' || self.ch == '\t' || self.ch == '\n' || self.ch == '\r' {
-            self.read_char()
+        loop {
+            match self.ch {
+                ' ' | '\t' | '\n' | '\r' => self.read_char(),
+                '/' if self.peek_char() == '/' && !self.at_doc_comment() => {
+                    self.read_comment();
+                }
+                '/' if self.peek_char() == '*' => self.skip_block_comment(),
+                _ => break,
+            }
+        }
+    }
+
+    /// Skips a `/* */` comment, which may contain other block comments.
+    fn skip_block_comment(&mut self) {
+        let (offset, line, column) = (self.position, self.line, self.column);
+        let mut depth = 0;
+        while self.position < self.input.len() {
+            if self.ch == '/' && self.peek_char() == '*' {
+                depth += 1;
+                self.read_char();
+            } else if self.ch == '*' && self.peek_char() == '/' {
+                depth -= 1;
+                self.read_char();
+                if depth == 0 {
+                    self.read_char();
+                    return;
+                }
+            }
+            self.read_char();
         }
+        let mut span = self.span_from(offset, line, column);
+        span.len = 2;
+        self.errors.push(Error::at(ErrorKind::UnterminatedComment, span));
     }
 
     fn peek_char(&self) -> char {
-        if self.read_position >= self.input.len() {
-            return 0 as char;
+        self.input[self.read_position..].chars().next().unwrap_or('\0')
+    }
+
+    fn span_from(&self, offset: usize, line: usize, column: usize) -> Span {
+        Span {
+            file: self.file.clone(),
+            offset: self.base + offset,
+            len: self.position.saturating_sub(offset),
+            line,
+            column,
         }
-        self.input.chars().nth(self.read_position).unwrap()
     }
 
-    pub fn next_token(&mut self) -> Token {
+    /// Reads the next token along with the span it covers.
+    pub fn next_token(&mut self) -> (Token, Span) {
         self.skip_whitespace();
+        let (offset, line, column) = (self.position, self.line, self.column);
+        self.token_start = (offset, line, column);
+        let tok = self.read_token();
+        (tok, self.span_from(offset, line, column))
+    }
+
+    /// ## two_char
+    /// Consumes the second character of an operator such as `+=` or `&&`.
+    ///
+    /// # Arguments
+    /// * `tok` - The token the whole operator stands for.
+    ///
+    /// # Returns
+    /// `tok`, so it can be used directly as a match arm.
+    fn two_char(&mut self, tok: Token) -> Token {
+        self.read_char();
+        tok
+    }
+
+    fn read_token(&mut self) -> Token {
         let tok: Token = match self.ch {
             '=' => {
                 if self.peek_char() == '=' {
@@ -136,12 +538,34 @@ impl Lexer {
             ';' => Token::Semicolon,
             ':' => Token::Colon,
             ',' => Token::Comma,
-            '+' => Token::Plus,
-            '-' => Token::Minus,
-            '&' => Token::AND,
-            '|' => Token::OR,
-            '^' => Token::XOR,
-            '%' => Token::Percent,
+            '+' => match self.peek_char() {
+                '+' => self.two_char(Token::Increment),
+                '=' => self.two_char(Token::PlusAssign),
+                _ => Token::Plus,
+            },
+            '-' => match self.peek_char() {
+                '-' => self.two_char(Token::Decrement),
+                '=' => self.two_char(Token::MinusAssign),
+                _ => Token::Minus,
+            },
+            '&' => match self.peek_char() {
+                '&' => self.two_char(Token::LogicalAnd),
+                '=' => self.two_char(Token::AndAssign),
+                _ => Token::AND,
+            },
+            '|' => match self.peek_char() {
+                '|' => self.two_char(Token::LogicalOr),
+                '=' => self.two_char(Token::OrAssign),
+                _ => Token::OR,
+            },
+            '^' => match self.peek_char() {
+                '=' => self.two_char(Token::XorAssign),
+                _ => Token::XOR,
+            },
+            '%' => match self.peek_char() {
+                '=' => self.two_char(Token::PercentAssign),
+                _ => Token::Percent,
+            },
             '!' => {
                 if self.peek_char() == '=' {
                     self.read_char();
@@ -151,39 +575,75 @@ impl Lexer {
                 }
             }
             '~' => Token::In,
+            '.' => match self.peek_char() {
+                '.' => {
+                    self.read_char();
+                    match self.peek_char() {
+                        '=' => self.two_char(Token::DotDotEq),
+                        _ => Token::DotDot,
+                    }
+                }
+                _ => Token::Illegal,
+            },
             '(' => Token::LeftParen,
             ')' => Token::RightParen,
             '{' => Token::LeftBrace,
             '}' => Token::RightBrace,
             '[' => Token::LeftBracket,
             ']' => Token::RightBracket,
-            '*' => Token::Asterisk,
+            '*' => match self.peek_char() {
+                '=' => self.two_char(Token::AsteriskAssign),
+                _ => Token::Asterisk,
+            },
             '/' => {
-                if self.peek_char() == '/' {
-                    self.read_comment();
-                    Token::Comment
+                if self.at_doc_comment() {
+                    let text = self.read_comment();
+                    let text = text[3..].strip_prefix(' ').unwrap_or(&text[3..]);
+                    Token::DocComment(text.trim_end().to_string())
+                } else if self.peek_char() == '=' {
+                    self.two_char(Token::SlashAssign)
                 } else {
                     Token::Slash
                 }
             }
             '<' => match self.peek_char() {
-                    '<' => Token::LeftShift,
-                    '=' => Token::LessEqual,
-                    _ => Token::Less
-                },
+                '<' => {
+                    self.read_char();
+                    match self.peek_char() {
+                        '=' => self.two_char(Token::LeftShiftAssign),
+                        _ => Token::LeftShift,
+                    }
+                }
+                '=' => self.two_char(Token::LessEqual),
+                _ => Token::Less,
+            },
             '>' => match self.peek_char() {
-                    '>' => Token::RightShift,
-                    '=' => Token::GreaterEqual,
-                    _ => Token::Greater
-                },
-            '"' => Token::String(self.read_string()),
+                '>' => {
+                    self.read_char();
+                    match self.peek_char() {
+                        '=' => self.two_char(Token::RightShiftAssign),
+                        _ => Token::RightShift,
+                    }
+                }
+                '=' => self.two_char(Token::GreaterEqual),
+                _ => Token::Greater,
+            },
+            '"' => Token::String(self.read_quoted(false)),
+            'r' if self.peek_char() == '"' => {
+                self.read_char();
+                Token::String(self.read_quoted(true))
+            }
+            '`' => Token::Template(self.read_template()),
             '\'' => {
-                if self.peek_char() == 's' {
-                    Token::SingleQuoteS
+                if is_letter(self.peek_char()) {
+                    // `cat's name` is a property, `'search` is a label.
+                    self.read_char();
+                    let name = self.read_identifier();
+                    return if name == "s" { Token::SingleQuoteS } else { Token::Label(name) };
                 } else {
                     Token::Illegal
                 }
-            } 
+            }
             '\u{0}' => Token::Eof,
             _ => {
                 if is_letter(self.ch) {
@@ -192,9 +652,8 @@ impl Lexer {
                         Some(a) => a.to_owned(),
                         _ => Token::Ident(i),
                     };
-                } else if self.ch.is_numeric() {
-                    let i: f64 = self.read_number();
-                    return Token::Number(i);
+                } else if self.ch.is_ascii_digit() {
+                    return self.read_number();
                 } else {
                     Token::Illegal
                 }
@@ -205,4 +664,66 @@ impl Lexer {
         tok
     }
 
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lexes all of `input`, giving back its tokens without the final `Eof`
+    /// and the kinds of the errors the lexer reported.
+    fn lex(input: &str) -> (Vec<Token>, Vec<ErrorKind>) {
+        let mut lexer = Lexer::new(input.to_string());
+        let mut tokens = vec![];
+        loop {
+            match lexer.next_token() {
+                (Token::Eof, _) => break,
+                (token, _) => tokens.push(token),
+            }
+        }
+        (tokens, lexer.errors.into_iter().map(|e| e.kind).collect())
+    }
+
+    fn string(s: &str) -> Token {
+        Token::String(String::from(s))
+    }
+
+    #[test]
+    fn numbers_can_be_written_in_other_bases_and_grouped() {
+        assert_eq!(lex("0xFF 0b1010 0o17 1_000_000"), (vec![Token::Int(255), Token::Int(10), Token::Int(15), Token::Int(1_000_000)], vec![]));
+        assert_eq!(lex("1.5e-3 2E10 3.25 1e+2"), (vec![Token::Number(1.5e-3), Token::Number(2e10), Token::Number(3.25), Token::Number(100.0)], vec![]));
+        assert_eq!(
+            lex("0x1_0000_0000_0000_0000"),
+            (vec![Token::BigInt(BigInt::from(u64::MAX) + 1)], vec![])
+        );
+    }
+
+    #[test]
+    fn malformed_numbers_are_reported_once() {
+        for input in ["1.2.3", "0b102", "0x", "1_000_", "1e"] {
+            let (tokens, errors) = lex(input);
+            assert_eq!(errors, vec![ErrorKind::MalformedNumber(String::from(input))], "{}", input);
+            assert_eq!(tokens, vec![Token::Int(0)], "{}", input);
+        }
+    }
+
+    #[test]
+    fn escape_sequences_become_the_characters_they_stand_for() {
+        assert_eq!(lex(r#""a\nb\tc\r\0""#), (vec![string("a\nb\tc\r\0")], vec![]));
+        assert_eq!(lex(r#""\\ \" \'""#), (vec![string("\\ \" '")], vec![]));
+        assert_eq!(lex(r#""\u{1F431}\u{41}""#), (vec![string("🐱A")], vec![]));
+    }
+
+    #[test]
+    fn bad_escape_sequences_are_reported() {
+        let (_, errors) = lex(r#""\q""#);
+        assert_eq!(errors, vec![ErrorKind::InvalidEscape(String::from("q"))]);
+        let (_, errors) = lex(r#""\u{110000}""#);
+        assert_eq!(errors, vec![ErrorKind::InvalidEscape(String::from("u{110000}"))]);
+    }
+
+    #[test]
+    fn unterminated_strings_are_reported() {
+        assert_eq!(lex(r#""abc"#), (vec![string("abc")], vec![ErrorKind::UnterminatedString]));
+        assert_eq!(lex("meow(\"abc);").1, vec![ErrorKind::UnterminatedString]);
+    }
+}
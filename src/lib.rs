@@ -1,4 +1,6 @@
 pub mod ast;
+pub mod diagnostic;
+pub mod error;
 pub mod evaluation;
 pub mod lexer;
 pub mod parser;
@@ -6,21 +8,49 @@ pub mod std_library;
 pub mod repl;
 use std::{rc::Rc, cell::RefCell};
 
+use diagnostic::{render, Sources};
 use evaluation::{object::*, store::*, *};
 use lexer::Lexer;
 use parser::Parser;
 
 pub fn interpret(content: &str) {
+    interpret_file("<main>", content)
+}
+
+/// ## document_file
+/// Prints a Markdown page with the doc comments of the top-level
+/// `scratch` bindings in `content`, one section per binding.
+/// Parse errors are reported against the file name `file` instead.
+pub fn document_file(file: &str, content: &str) {
+    let mut sources = Sources::new();
+    sources.add(file, content);
+    let lexer = Lexer::with_file(content.to_string(), file);
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+    if !parser.errors.is_empty() {
+        for e in parser.errors.iter() {
+            println!("{}", render(e, &mut sources));
+        }
+        return;
+    }
+    println!("# {}", file);
+    for (name, doc) in program.docs() {
+        println!("\n## {}\n\n{}", name, doc);
+    }
+}
+
+/// Interprets `content`, reporting diagnostics against the file name `file`.
+pub fn interpret_file(file: &str, content: &str) {
+    let mut sources = Sources::new();
+    sources.add(file, content);
     let store = Store::new();
-    let mut evaluator = Eval {
-        store: Rc::new(RefCell::new(store)),
-    };
-    let lexer = Lexer::new(content.to_string());
+    let mut evaluator = Eval::new(Rc::new(RefCell::new(store)));
+    let lexer = Lexer::with_file(content.to_string(), file);
     let mut parser = Parser::new(lexer);
     let program = parser.parse_program();
     if !parser.errors.is_empty() {
         for e in parser.errors.iter() {
-            println!("\t{}", e);
+            println!("{}", render(e, &mut sources));
         }
         return;
     }
@@ -29,6 +59,7 @@ pub fn interpret(content: &str) {
     if let Some(o) = res {
         match o {
             Object::Null => (),
+            Object::Error(e) => println!("{}", render(&e, &mut sources)),
             _ => println!("{}", o),
         }
     }
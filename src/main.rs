@@ -22,7 +22,11 @@ fn main() {
         }
         let content = fs::read_to_string(&args[2]).expect("Could not read file.");
 
-        meowscript::interpret(content.as_str());
+        meowscript::interpret_file(&args[2], content.as_str());
+    } else if args.len() > 2 && args[1].as_str() == "doc" {
+        let content = fs::read_to_string(&args[2]).expect("Could not read file.");
+
+        meowscript::document_file(&args[2], content.as_str());
     } else {
     
         println!(
@@ -1,20 +1,43 @@
-use crate::{ast::{*, token::Token}, lexer::Lexer};
+use crate::{ast::{*, token::{Span, TemplatePart, Token}}, error::{Error, ErrorKind}, lexer::Lexer};
 
 pub struct Parser {
     lexer: Lexer,
     current_token: Token,
+    current_span: Span,
     peek_token: Token,
-    pub errors: Vec<String>
+    peek_span: Span,
+    /// The `///` doc comment written right before the current token, if any.
+    current_doc: Option<String>,
+    peek_doc: Option<String>,
+    pub errors: Vec<Error>,
+    /// Set after an error until the parser has skipped to a point it can resume from,
+    /// so a single mistake is only reported once.
+    recovering: bool,
+    /// The loops around the current token with their labels, innermost last.
+    loops: Vec<Option<String>>,
 }
 
 impl Parser {
 
     pub fn new(lexer: Lexer) -> Self {
+        let span = Span {
+            file: "".into(),
+            offset: 0,
+            len: 0,
+            line: 1,
+            column: 1,
+        };
         let mut p: Parser = Parser {
             lexer,
             current_token: Token::Eof,
+            current_span: span.clone(),
             peek_token: Token::Eof,
+            peek_span: span,
+            current_doc: None,
+            peek_doc: None,
             errors: vec![],
+            recovering: false,
+            loops: vec![],
         };
         p.next_token();
         p.next_token();
@@ -22,17 +45,38 @@ impl Parser {
     }
 
     pub fn next_token(&mut self) {
-        self.current_token = self.peek_token.clone();
-        self.peek_token = self.lexer.next_token();
+        let mut doc: Vec<String> = vec![];
+        let (token, span) = loop {
+            match self.lexer.next_token() {
+                (Token::DocComment(line), _) => doc.push(line),
+                next => break next,
+            }
+        };
+        let doc = if doc.is_empty() { None } else { Some(doc.join("\n")) };
+        // The lexer already reported what is wrong with the token, anything
+        // the parser would say about it is only a consequence of that.
+        if !self.lexer.errors.is_empty() {
+            self.recovering = true;
+        }
+        self.errors.append(&mut self.lexer.errors);
+        self.current_token = std::mem::replace(&mut self.peek_token, token);
+        self.current_span = std::mem::replace(&mut self.peek_span, span);
+        self.current_doc = std::mem::replace(&mut self.peek_doc, doc);
     }
 
+    /// ## parse_program
+    /// Parses every statement up to the end of the input.
+    /// Statements that fail to parse are reported in `errors` and skipped,
+    /// so the returned program holds everything that did parse.
     pub fn parse_program(&mut self) -> Program {
         let mut statements: Vec<Statement> = vec![];
         while self.current_token != Token::Eof {
-            let stmt: Option<Statement> = self.parse_statement();
-            if stmt != None {
-                statements.push(stmt.unwrap());
-            };
+            if let Some(stmt) = self.parse_statement() {
+                statements.push(stmt);
+            }
+            if self.recovering {
+                self.synchronize();
+            }
             self.next_token();
         }
         Program { statements }
@@ -46,6 +90,8 @@ impl Parser {
             Token::Anew => self.parse_anew_expr(),
             Token::Break => self.parse_break_statement(),
             Token::Continue => self.parse_continue_statement(),
+            Token::Throw => self.parse_throw_statement(),
+            Token::Semicolon => None,
             _ => self.parse_expr_statement(),
         }
     }
@@ -53,17 +99,24 @@ impl Parser {
 
     pub fn parse_expr_statement(&mut self) -> Option<Statement> {
         match self.parse_expr(Precedence::Lowest) {
+            Some(target) if Self::is_compound_assign(&self.peek_token) => {
+                let start = target.span.clone();
+                self.parse_assignment(start, target)
+            }
             Some(expression) => {
                 if self.peek_token(&Token::Semicolon) {
                     self.next_token();
                 }
-                Some(Statement::Expression(expression))
+                let span = expression.span.to(&self.current_span);
+                Some(Statement::new(StatementKind::Expression(expression), span))
             }
             None => None,
         }
     }
 
     pub fn parse_set_statement(&mut self) -> Option<Statement> {
+        let start = self.current_span.clone();
+        let doc = self.current_doc.clone();
         match &self.peek_token {
             Token::Ident(_) => self.next_token(),
             _ => {
@@ -73,8 +126,14 @@ impl Parser {
         }
 
         let name: Ident = match self.parse_ident() {
-            Some(Expr::Ident(ref mut s)) => s.clone(),
-            _ => return None,
+            Some(Expr { kind: ExprKind::Ident(ref mut s), .. }) => s.clone(),
+            _ => {
+                self.error_at_current(ErrorKind::UnexpectedToken {
+                    expected: String::from("a plain identifier after scratch"),
+                    found: self.current_token.to_string(),
+                });
+                return None;
+            }
         };
 
         if !self.expect_peek(Token::Assign) {
@@ -88,14 +147,17 @@ impl Parser {
             None => return None,
         };
 
-        while !self.current_token(Token::Semicolon) {
-            self.next_token();
-        }
+        self.end_statement();
 
-        Some(Statement::Set(name, lit))
+        Some(Statement::new(StatementKind::Set(name, lit, doc), self.span_from(&start)))
     }
 
     pub fn parse_return_statement(&mut self) -> Option<Statement> {
+        let start = self.current_span.clone();
+        if matches!(self.peek_token, Token::Semicolon | Token::RightBrace | Token::Eof) {
+            self.end_statement();
+            return Some(Statement::new(StatementKind::Return(None), self.span_from(&start)));
+        }
         self.next_token();
 
         let exp = match self.parse_expr(Precedence::Lowest) {
@@ -103,27 +165,27 @@ impl Parser {
             None => return None,
         };
 
-        while !self.current_token(Token::Semicolon) {
-            self.next_token();
-        }
+        self.end_statement();
 
-        Some(Statement::Return(exp))
+        Some(Statement::new(StatementKind::Return(Some(exp)), self.span_from(&start)))
     }
 
     pub fn parse_include_statement(&mut self) -> Option<Statement> {
+        let start = self.current_span.clone();
         self.next_token();
         // the string next to the include keyword is the lib
         let lib = match &self.current_token {
             Token::String(ref s) => s.clone(),
             _ => {
-                self.peek_error(Token::String(String::new()));
+                self.error_at_current(ErrorKind::UnexpectedToken {
+                    expected: format!("{} after pawckage", Token::String(String::new())),
+                    found: self.current_token.to_string(),
+                });
                 return None;
-            } 
+            }
         };
-        while !self.current_token(Token::Semicolon) {
-            self.next_token();
-        }
-        Some(Statement::Include(lib))
+        self.end_statement();
+        Some(Statement::new(StatementKind::Include(lib), self.span_from(&start)))
     }
 
     fn parse_block_statement(&mut self) -> BlockStatement {
@@ -134,13 +196,24 @@ impl Parser {
             if let Some(s) = self.parse_statement() {
                 statements.push(s);
             }
+            if self.recovering {
+                self.synchronize();
+            }
             self.next_token();
         }
 
+        if self.current_token(Token::Eof) {
+            self.error_at_current(ErrorKind::UnexpectedToken {
+                expected: format!("{} to close the block", Token::RightBrace),
+                found: self.current_token.to_string(),
+            });
+        }
+
         statements
     }
 
     pub fn parse_anew_expr(&mut self) -> Option<Statement> {
+        let start = self.current_span.clone();
         match &self.peek_token {
             Token::Ident(_) => self.next_token(),
             _ => {
@@ -149,58 +222,315 @@ impl Parser {
             }
         }
 
-        let name: Ident = match self.parse_ident() {
-            Some(Expr::Ident(ref mut s)) => s.clone(),
-            _ => return None,
-        };
+        // Only indexes and properties bind tighter than calls, so this stops
+        // right before the `=` of `amew cat's toys[0] = ...`.
+        let target = self.parse_expr(Precedence::Call)?;
+        self.parse_assignment(start, target)
+    }
 
-        if !self.expect_peek(Token::Assign) {
+    /// ## parse_assignment
+    /// Parses the operator and value of an assignment to `target`, with the
+    /// operator as the peek token. Compound assignments keep their operator, so
+    /// `x += 1` and `x++` both add `1` to `x` in whichever scope declared it,
+    /// working out the target's indexes only once.
+    ///
+    /// # Arguments
+    /// * `start` - The span the statement starts at, either `amew` or the target.
+    /// * `target` - The variable, index or property being assigned to.
+    ///
+    /// # Returns
+    /// An `Anew` statement, or `None` if the assignment is malformed.
+    fn parse_assignment(&mut self, start: Span, target: Expr) -> Option<Statement> {
+        if !Self::is_assignable(&target) {
+            let kind = ErrorKind::UnexpectedToken {
+                expected: String::from("a variable, index or property to assign to"),
+                found: String::from("an expression"),
+            };
+            self.error(kind, target.span);
             return None;
         }
 
+        let infix = match &self.peek_token {
+            Token::Assign => None,
+            Token::Increment => Some(Infix::Plus),
+            Token::Decrement => Some(Infix::Minus),
+            tok => match Self::compound_operator(tok) {
+                Some(infix) => Some(infix),
+                None => {
+                    self.peek_error(Token::Assign);
+                    return None;
+                }
+            },
+        };
         self.next_token();
 
-        let lit: Expr = match self.parse_expr(Precedence::Lowest) {
-            Some(e) => e,
-            None => return None,
+        let value = if matches!(self.current_token, Token::Increment | Token::Decrement) {
+            Expr::new(ExprKind::Literal(Literal::Int(1)), self.current_span.clone())
+        } else {
+            self.next_token();
+            self.parse_expr(Precedence::Lowest)?
         };
+        self.end_statement();
 
-        while !self.current_token(Token::Semicolon) {
-            self.next_token();
+        Some(Statement::new(StatementKind::Anew(target, infix, value), self.span_from(&start)))
+    }
+
+    /// ## is_assignable
+    /// Checks whether `target` is a variable, or an index or property of one.
+    fn is_assignable(target: &Expr) -> bool {
+        match &target.kind {
+            ExprKind::Ident(_) => true,
+            ExprKind::Index { array, .. } => Self::is_assignable(array),
+            _ => false,
         }
+    }
+
+    /// ## is_compound_assign
+    /// Checks whether `tok` is `++`, `--` or an operator like `+=`.
+    fn is_compound_assign(tok: &Token) -> bool {
+        matches!(tok, Token::Increment | Token::Decrement) || Self::compound_operator(tok).is_some()
+    }
 
-        Some(Statement::Anew(name, lit))
+    /// ## compound_operator
+    /// Maps a compound assignment such as `<<=` to the infix operator it applies.
+    fn compound_operator(tok: &Token) -> Option<Infix> {
+        match tok {
+            Token::PlusAssign => Some(Infix::Plus),
+            Token::MinusAssign => Some(Infix::Minus),
+            Token::AsteriskAssign => Some(Infix::Times),
+            Token::SlashAssign => Some(Infix::Divide),
+            Token::PercentAssign => Some(Infix::Modulo),
+            Token::AndAssign => Some(Infix::AND),
+            Token::OrAssign => Some(Infix::OR),
+            Token::XorAssign => Some(Infix::XOR),
+            Token::LeftShiftAssign => Some(Infix::LeftShift),
+            Token::RightShiftAssign => Some(Infix::RightShift),
+            _ => None,
+        }
     }
 
+    /// ## parse_break_statement
+    /// Parses `hiss;`, optionally followed by the label of the loop to leave
+    /// and a value for the loop to evaluate to, as in `hiss 'outer found;`.
     pub fn parse_break_statement(&mut self) -> Option<Statement> {
-        self.next_token();
-        while !self.current_token(Token::Semicolon) {
+        let start = self.current_span.clone();
+        let label = self.parse_jump_label("hiss")?;
+        let value = if matches!(self.peek_token, Token::Semicolon | Token::RightBrace | Token::Eof) {
+            None
+        } else {
             self.next_token();
-        }
-        Some(Statement::Break)
+            Some(self.parse_expr(Precedence::Lowest)?)
+        };
+        self.end_statement();
+        Some(Statement::new(StatementKind::Break(label, value), self.span_from(&start)))
     }
 
+    /// ## parse_continue_statement
+    /// Parses `continue;`, optionally followed by the label of the loop to continue.
     pub fn parse_continue_statement(&mut self) -> Option<Statement> {
+        let start = self.current_span.clone();
+        let label = self.parse_jump_label("continue")?;
+        self.end_statement();
+        Some(Statement::new(StatementKind::Continue(label), self.span_from(&start)))
+    }
+
+    /// ## parse_jump_label
+    /// Parses the label after `hiss` or `continue`, if there is one.
+    /// # Arguments
+    /// * `keyword` - The keyword the label follows, for errors.
+    /// # Returns
+    /// `Some(None)` without a label, `None` if there is no loop around it with that label.
+    fn parse_jump_label(&mut self, keyword: &str) -> Option<Option<String>> {
+        let label = match &self.peek_token {
+            Token::Label(label) => label.clone(),
+            _ if self.loops.is_empty() => {
+                self.error_at_current(ErrorKind::OutsideLoop(keyword.to_string()));
+                return None;
+            }
+            _ => return Some(None),
+        };
         self.next_token();
-        while !self.current_token(Token::Semicolon) {
-            self.next_token();
+        if !self.loops.contains(&Some(label.clone())) {
+            self.error_at_current(ErrorKind::UnknownLabel(label));
+            return None;
         }
-        Some(Statement::Continue)
+        Some(Some(label))
+    }
+
+    pub fn parse_throw_statement(&mut self) -> Option<Statement> {
+        let start = self.current_span.clone();
+        self.next_token();
+
+        let exp = match self.parse_expr(Precedence::Lowest) {
+            Some(e) => e,
+            None => return None,
+        };
+
+        self.end_statement();
+
+        Some(Statement::new(StatementKind::Throw(exp), self.span_from(&start)))
     }
 
     fn parse_typof_expr(&mut self) -> Option<Expr> {
+        let start = self.current_span.clone();
         self.next_token();
-        let expr = match self.parse_expr(Precedence::Lowest) {
+        let expr = match self.parse_expr(Precedence::Prefix) {
             Some(e) => e,
             None => return None,
         };
-        Some(Expr::Typeof { expr: Box::new(expr) })
+        Some(Expr::new(ExprKind::Typeof { expr: Box::new(expr) }, self.span_from(&start)))
+    }
+
+    /// ## parse_labeled_loop
+    /// Parses a loop with a label in front of it, like `'outer: furrever { ... }`.
+    /// The label can be used by `hiss` and `continue` anywhere in the loop's body,
+    /// but not inside pawctions defined there.
+    fn parse_labeled_loop(&mut self) -> Option<Expr> {
+        let start = self.current_span.clone();
+        let label = match &self.current_token {
+            Token::Label(label) => label.clone(),
+            _ => return None,
+        };
+        if !self.expect_peek(Token::Colon) {
+            return None;
+        }
+        if !matches!(self.peek_token, Token::Loop | Token::While | Token::For) {
+            self.error_at_peek(ErrorKind::UnexpectedToken {
+                expected: format!("a loop after the label '{}", label),
+                found: self.peek_token.to_string(),
+            });
+            return None;
+        }
+        self.next_token();
+
+        let expr = match self.current_token {
+            Token::Loop => self.parse_loop_expr(Some(label)),
+            Token::While => self.parse_while_expr(Some(label)),
+            _ => self.parse_for_expr(Some(label)),
+        };
+
+        expr.map(|e| Expr::new(e.kind, self.span_from(&start)))
+    }
+
+    fn parse_loop_expr(&mut self, label: Option<String>) -> Option<Expr> {
+        let start = self.current_span.clone();
+        self.next_token();
+        let body = self.parse_loop_body(&label);
+        Some(Expr::new(ExprKind::Loop { body, label }, self.span_from(&start)))
     }
 
-    fn parse_loop_expr(&mut self) -> Option<Expr> {
+    /// ## parse_loop_body
+    /// Parses the block of a loop, inside which `hiss` and `continue` can jump to it.
+    fn parse_loop_body(&mut self, label: &Option<String>) -> BlockStatement {
+        self.loops.push(label.clone());
+        let body = self.parse_block_statement();
+        self.loops.pop();
+        body
+    }
+
+    /// ## parse_while_expr
+    /// Parses `purrsist (cond) { ... }`.
+    fn parse_while_expr(&mut self, label: Option<String>) -> Option<Expr> {
+        let start = self.current_span.clone();
+        if !self.expect_peek(Token::LeftParen) {
+            return None;
+        }
+        self.next_token();
+        let cond = self.parse_expr(Precedence::Lowest)?;
+        if !self.expect_peek(Token::RightParen) || !self.expect_peek(Token::LeftBrace) {
+            return None;
+        }
+        let body = self.parse_loop_body(&label);
+        Some(Expr::new(ExprKind::While { cond: Box::new(cond), body, label }, self.span_from(&start)))
+    }
+
+    /// ## parse_for_expr
+    /// Parses `fur (binding ~ iterable) { ... }`.
+    fn parse_for_expr(&mut self, label: Option<String>) -> Option<Expr> {
+        let start = self.current_span.clone();
+        if !self.expect_peek(Token::LeftParen) {
+            return None;
+        }
+        let binding = match self.peek_token.clone() {
+            Token::Ident(name) => {
+                self.next_token();
+                Ident(name)
+            }
+            _ => {
+                self.peek_error(Token::Ident(String::new()));
+                return None;
+            }
+        };
+        if !self.expect_peek(Token::In) {
+            return None;
+        }
         self.next_token();
+        let iterable = self.parse_expr(Precedence::Lowest)?;
+        if !self.expect_peek(Token::RightParen) || !self.expect_peek(Token::LeftBrace) {
+            return None;
+        }
+        let body = self.parse_loop_body(&label);
+        Some(Expr::new(
+            ExprKind::For { binding, iterable: Box::new(iterable), body, label },
+            self.span_from(&start),
+        ))
+    }
+
+    /// ## parse_try_expr
+    /// Parses `attempurr { ... } catch (e) { ... } finurrly { ... }`.
+    /// The binding after `catch` is optional, and either `catch` or `finurrly`
+    /// may be left out, but not both.
+    fn parse_try_expr(&mut self) -> Option<Expr> {
+        let start = self.current_span.clone();
+        if !self.expect_peek(Token::LeftBrace) {
+            return None;
+        }
         let body = self.parse_block_statement();
-        Some(Expr::Loop { body })
+
+        let mut catch = None;
+        if self.peek_token(&Token::Catch) {
+            self.next_token();
+            let mut binding = None;
+            if self.peek_token(&Token::LeftParen) {
+                self.next_token();
+                match self.peek_token.clone() {
+                    Token::Ident(name) => {
+                        self.next_token();
+                        binding = Some(Ident(name));
+                    }
+                    _ => {
+                        self.peek_error(Token::Ident(String::new()));
+                        return None;
+                    }
+                }
+                if !self.expect_peek(Token::RightParen) {
+                    return None;
+                }
+            }
+            if !self.expect_peek(Token::LeftBrace) {
+                return None;
+            }
+            catch = Some((binding, self.parse_block_statement()));
+        }
+
+        let mut finally = None;
+        if self.peek_token(&Token::Finally) {
+            self.next_token();
+            if !self.expect_peek(Token::LeftBrace) {
+                return None;
+            }
+            finally = Some(self.parse_block_statement());
+        }
+
+        if catch.is_none() && finally.is_none() {
+            self.error_at_peek(ErrorKind::UnexpectedToken {
+                expected: format!("{} or {} after attempurr", Token::Catch, Token::Finally),
+                found: self.peek_token.to_string(),
+            });
+            return None;
+        }
+
+        Some(Expr::new(ExprKind::Try { body, catch, finally }, self.span_from(&start)))
     }
 
     fn parse_expr(&mut self, precedence: Precedence) -> Option<Expr> {
@@ -210,28 +540,44 @@ impl Parser {
             Token::LeftParen => self.parse_grouped_expr(),
             Token::If => self.parse_if_expr(),
             Token::Func => self.parse_fn_expr(),
-            Token::Number(_) => self.parse_int_literal(),
+            Token::Int(_) | Token::BigInt(_) | Token::Number(_) => self.parse_int_literal(),
             Token::Boolean(_) => self.parse_boolean_literal(),
             Token::String(_) => self.parse_string_literal(),
+            Token::Template(_) => self.parse_template_literal(),
             Token::LeftBracket => self.parse_array_literal(),
             Token::LeftBrace => self.parse_object_literal(),
             Token::Typeof => self.parse_typof_expr(),
-            Token::Loop => self.parse_loop_expr(),
+            Token::Loop => self.parse_loop_expr(None),
+            Token::While => self.parse_while_expr(None),
+            Token::For => self.parse_for_expr(None),
+            Token::Label(_) => self.parse_labeled_loop(),
+            Token::Try => self.parse_try_expr(),
             Token::SingleQuoteS => {
+                let start = self.current_span.clone();
                 self.next_token();
                 match self.parse_ident() {
-                    Some(Expr::Ident(ident)) => Some(Expr::Index {
-                        array: Box::new(Expr::Ident(Ident("self".to_string()))), // Assuming 'self' is the dictionary object
-                        index: Box::new(Expr::Literal(Literal::String(ident.0))),
-                    }),
+                    Some(Expr { kind: ExprKind::Ident(ident), span }) => Some(Expr::new(
+                        ExprKind::Index {
+                            array: Box::new(Expr::new(ExprKind::Ident(Ident("self".to_string())), start.clone())), // Assuming 'self' is the dictionary object
+                            index: Box::new(Expr::new(ExprKind::Literal(Literal::String(ident.0)), span)),
+                        },
+                        self.span_from(&start),
+                    )),
                     _ => None,
                 }
             }
             _ => {
+                self.error_at_current(ErrorKind::UnexpectedToken {
+                    expected: String::from("an expression"),
+                    found: self.current_token.to_string(),
+                });
                 None
             }
         };
 
+        // The operand could not be parsed, there is nothing to apply operators to.
+        left.as_ref()?;
+
         while !self.peek_token(&Token::Semicolon) && precedence < self.next_token_precedence() {
             match self.peek_token {
                 Token::Plus
@@ -248,21 +594,17 @@ impl Parser {
                 | Token::AND
                 | Token::OR
                 | Token::XOR
+                | Token::LeftShift
+                | Token::RightShift
+                | Token::LogicalAnd
+                | Token::LogicalOr
                 | Token::In => {
                     self.next_token();
                     left = self.parse_infix_expr(left.unwrap());
                 }
-
-                Token::LeftShift => {
-                    self.lexer.next_token();
+                Token::DotDot | Token::DotDotEq => {
                     self.next_token();
-                    left = self.parse_infix_expr(left.unwrap());
-                }
-
-                Token::RightShift => {
-                    self.lexer.next_token();
-                    self.next_token();
-                    left = self.parse_infix_expr(left.unwrap());
+                    left = self.parse_range_expr(left.unwrap());
                 }
 
                 Token::LeftParen => {
@@ -273,6 +615,10 @@ impl Parser {
                     self.next_token();
                     left = self.parse_index_expr(left.unwrap());
                 }
+                Token::SingleQuoteS => {
+                    self.next_token();
+                    left = self.parse_property_expr(left.unwrap());
+                }
                 _ => return left,
             }
         }
@@ -281,6 +627,7 @@ impl Parser {
     }
 
     fn parse_object_literal(&mut self) -> Option<Expr> {
+        let start = self.current_span.clone();
         let mut obj = vec![];
         while !self.peek_token(&Token::RightBrace) {
             self.next_token();
@@ -306,36 +653,79 @@ impl Parser {
             return None;
         }
 
-        Some(Expr::Literal(Literal::Object(obj)))
+        Some(Expr::new(ExprKind::Literal(Literal::Object(obj)), self.span_from(&start)))
     }
 
     fn parse_int_literal(&mut self) -> Option<Expr> {
         match self.current_token {
-            Token::Number(ref mut int) => Some(Expr::Literal(Literal::Number(*int))),
+            Token::Int(int) => Some(Expr::new(ExprKind::Literal(Literal::Int(int)), self.current_span.clone())),
+            Token::BigInt(ref int) => Some(Expr::new(ExprKind::Literal(Literal::BigInt(int.clone())), self.current_span.clone())),
+            Token::Number(ref mut int) => Some(Expr::new(ExprKind::Literal(Literal::Number(*int)), self.current_span.clone())),
             _ => None,
         }
     }
 
     fn parse_boolean_literal(&mut self) -> Option<Expr> {
         match self.current_token {
-            Token::Boolean(boolean) => Some(Expr::Literal(Literal::Boolean(boolean))),
+            Token::Boolean(boolean) => Some(Expr::new(ExprKind::Literal(Literal::Boolean(boolean)), self.current_span.clone())),
             _ => None,
         }
     }
 
     fn parse_string_literal(&mut self) -> Option<Expr> {
         match self.current_token {
-            Token::String(ref mut str) => Some(Expr::Literal(Literal::String(str.clone()))),
+            Token::String(ref mut str) => Some(Expr::new(ExprKind::Literal(Literal::String(str.clone())), self.current_span.clone())),
             _ => None,
         }
     }
 
+    /// ## parse_template_literal
+    /// Parses a template string. Each `${...}` is parsed on its own,
+    /// with a nested lexer so its spans point into the template.
+    fn parse_template_literal(&mut self) -> Option<Expr> {
+        let parts = match self.current_token {
+            Token::Template(ref parts) => parts.clone(),
+            _ => return None,
+        };
+        let mut exprs = vec![];
+        for part in parts {
+            match part {
+                TemplatePart::Text(s) => exprs.push(Expr::new(
+                    ExprKind::Literal(Literal::String(s)),
+                    self.current_span.clone(),
+                )),
+                TemplatePart::Code { source, offset, line, column } => {
+                    let span = Span {
+                        file: self.current_span.file.clone(),
+                        offset,
+                        len: source.len(),
+                        line,
+                        column,
+                    };
+                    let mut parser = Parser::new(Lexer::nested(source, &span));
+                    let expr = parser.parse_expr(Precedence::Lowest);
+                    if expr.is_some() && !parser.peek_token(&Token::Eof) {
+                        let found = parser.peek_token.to_string();
+                        parser.error_at_peek(ErrorKind::UnexpectedToken {
+                            expected: "} to close the ${ in a template string".to_string(),
+                            found,
+                        });
+                    }
+                    self.errors.append(&mut parser.errors);
+                    exprs.extend(expr);
+                }
+            }
+        }
+        Some(Expr::new(ExprKind::Template(exprs), self.current_span.clone()))
+    }
+
     fn parse_array_literal(&mut self) -> Option<Expr> {
+        let start = self.current_span.clone();
         self.parse_expr_list(Token::RightBracket)
-            .map(|list| Expr::Literal(Literal::Array(list)))
+            .map(|list| Expr::new(ExprKind::Literal(Literal::Array(list)), self.span_from(&start)))
     }
 
-    
+
 
     fn parse_expr_list(&mut self, end: Token) -> Option<Vec<Expr>> {
         let mut list = vec![];
@@ -370,36 +760,46 @@ impl Parser {
     }
 
     fn parse_ident(&mut self) -> Option<Expr> {
-        match self.current_token {
-            Token::Ident(_) => {
-                let ident = match &self.current_token {
-                    Token::Ident(ident) => ident.clone(),
-                    _ => return None,
-                };
-                if self.peek_token(&Token::SingleQuoteS) {
-                    self.next_token(); 
-                    self.next_token();
-                    if let Token::Ident(prop) = &self.peek_token {
-                        let property = prop.clone();
-                        self.next_token();
-                        return Some(Expr::Index {
-                            array: Box::new(Expr::Ident(Ident(ident))),
-                            index: Box::new(Expr::Literal(Literal::String(property))),
-                        });
-                    } else {
-                        self.errors.push(format!("Expected an identifier after '"));
-                        return None;
-                    }
-                } else {
-                    return Some(Expr::Ident(Ident(ident)));
-                }
-            }
+        match &self.current_token {
+            Token::Ident(ident) => Some(Expr::new(ExprKind::Ident(Ident(ident.clone())), self.current_span.clone())),
             _ => None,
         }
     }
-    
+
+    /// ## parse_property_expr
+    /// Parses `left's name`, which reads the `"name"` key of `left`.
+    ///
+    /// # Arguments
+    /// * `left` - The object the property is read from.
+    ///
+    /// # Returns
+    /// An index expression with the property name as its key.
+    fn parse_property_expr(&mut self, left: Expr) -> Option<Expr> {
+        let property = match &self.peek_token {
+            Token::Ident(prop) => prop.clone(),
+            _ => {
+                self.error_at_peek(ErrorKind::UnexpectedToken {
+                    expected: String::from("an identifier after 's"),
+                    found: self.peek_token.to_string(),
+                });
+                return None;
+            }
+        };
+        self.next_token();
+
+        let span = left.span.to(&self.current_span);
+        Some(Expr::new(
+            ExprKind::Index {
+                array: Box::new(left),
+                index: Box::new(Expr::new(ExprKind::Literal(Literal::String(property)), self.current_span.clone())),
+            },
+            span,
+        ))
+    }
+
 
     fn parse_prefix_expr(&mut self) -> Option<Expr> {
+        let start = self.current_span.clone();
         let prefix = match self.current_token {
             Token::Bang => Prefix::Exclamation,
             Token::Minus => Prefix::Minus,
@@ -410,7 +810,20 @@ impl Parser {
         self.next_token();
 
         self.parse_expr(Precedence::Prefix)
-            .map(|expr| Expr::Prefix(prefix, Box::new(expr)))
+            .map(|expr| Expr::new(ExprKind::Prefix(prefix, Box::new(expr)), self.span_from(&start)))
+    }
+
+    /// ## parse_range_expr
+    /// Parses the end of `start..end` or `start..=end`, with the `..` as the current token.
+    fn parse_range_expr(&mut self, start: Expr) -> Option<Expr> {
+        let inclusive = self.current_token(Token::DotDotEq);
+        self.next_token();
+        let end = self.parse_expr(Precedence::Range)?;
+        let span = start.span.to(&end.span);
+        Some(Expr::new(
+            ExprKind::Range { start: Box::new(start), end: Box::new(end), inclusive },
+            span,
+        ))
     }
 
     fn parse_infix_expr(&mut self, left: Expr) -> Option<Expr> {
@@ -431,6 +844,8 @@ impl Parser {
             Token::AND => Infix::AND,
             Token::OR => Infix::OR,
             Token::XOR => Infix::XOR,
+            Token::LogicalAnd => Infix::LogicalAnd,
+            Token::LogicalOr => Infix::LogicalOr,
             Token::In => Infix::In,
             _ => return None,
         };
@@ -438,19 +853,24 @@ impl Parser {
         let precedence = self.current_token_precedence();
         self.next_token();
 
-        self.parse_expr(precedence)
-            .map(|e| Expr::Infix(infix, Box::new(left), Box::new(e)))
+        self.parse_expr(precedence).map(|e| {
+            let span = left.span.to(&e.span);
+            Expr::new(ExprKind::Infix(infix, Box::new(left), Box::new(e)), span)
+        })
     }
 
     fn parse_grouped_expr(&mut self) -> Option<Expr> {
+        let start = self.current_span.clone();
+        self.next_token();
         let exp = self.parse_expr(Precedence::Lowest);
         if !self.expect_peek(Token::RightParen) {
             return None;
         }
-        exp
+        exp.map(|e| Expr::new(e.kind, self.span_from(&start)))
     }
 
     fn parse_if_expr(&mut self) -> Option<Expr> {
+        let start = self.current_span.clone();
         if !self.expect_peek(Token::LeftParen) {
             return None;
         }
@@ -473,8 +893,9 @@ impl Parser {
 
             if self.peek_token(&Token::If) {
                 self.next_token();
-                let else_if = self.parse_if_expr();
-                alternative = Some(vec![Statement::Expression(else_if.unwrap())]);
+                let else_if = self.parse_if_expr()?;
+                let span = else_if.span.clone();
+                alternative = Some(vec![Statement::new(StatementKind::Expression(else_if), span)]);
             } else if !self.expect_peek(Token::LeftBrace) {
                 return None;
             } else {
@@ -482,14 +903,18 @@ impl Parser {
             };
         }
 
-        Some(Expr::If {
-            cond: Box::new(expr),
-            then: Box::new(cons),
-            else_: alternative,
-        })
+        Some(Expr::new(
+            ExprKind::If {
+                cond: Box::new(expr),
+                then: Box::new(cons),
+                else_: alternative,
+            },
+            self.span_from(&start),
+        ))
     }
 
     fn parse_fn_expr(&mut self) -> Option<Expr> {
+        let start = self.current_span.clone();
         if !self.expect_peek(Token::LeftParen) {
             return None;
         }
@@ -497,27 +922,34 @@ impl Parser {
             Some(s) => s,
             None => return None,
         };
-        self.next_token();
+        if !self.expect_peek(Token::LeftBrace) {
+            return None;
+        }
+        // Loops outside of the pawction cannot be jumped to from inside it.
+        let loops = std::mem::take(&mut self.loops);
         let body = self.parse_block_statement();
-        
-        Some(Expr::Fun { params, body })
+        self.loops = loops;
+
+        Some(Expr::new(ExprKind::Fun { params, body }, self.span_from(&start)))
     }
 
     fn token_to_precedence(tok: &Token) -> Precedence {
         match tok {
+            Token::LogicalOr => Precedence::LogicalOr,
+            Token::LogicalAnd => Precedence::LogicalAnd,
             Token::Equals | Token::NotEquals => Precedence::Equals,
             Token::Less | Token::LessEqual => Precedence::LessGreater,
             Token::Greater | Token::GreaterEqual => Precedence::LessGreater,
+            Token::In => Precedence::LessGreater,
+            Token::DotDot | Token::DotDotEq => Precedence::Range,
+            Token::OR => Precedence::BitOr,
+            Token::XOR => Precedence::BitXor,
+            Token::AND => Precedence::BitAnd,
+            Token::LeftShift | Token::RightShift => Precedence::Shift,
             Token::Plus | Token::Minus => Precedence::Sum,
             Token::Slash | Token::Asterisk | Token::Percent => Precedence::Product,
-            Token::LeftBracket => Precedence::Index,
             Token::LeftParen => Precedence::Call,
-            Token::In => Precedence::In,
-            Token::LeftShift => Precedence::LeftShift,
-            Token::RightShift => Precedence::RightShift,
-            Token::AND => Precedence::AND,
-            Token::OR => Precedence::OR,
-            Token::XOR => Precedence::XOR,
+            Token::LeftBracket | Token::SingleQuoteS => Precedence::Index,
             _ => Precedence::Lowest,
         }
     }
@@ -533,9 +965,10 @@ impl Parser {
         match self.current_token {
             Token::Ident(ref mut ident) => idents.push(Ident(ident.clone())),
             _ => {
-                self.errors.push(
-                   format!("Meow! Expected a purr-ameter identifier. Got: {} 🙀", self.current_token)
-                );
+                self.error_at_current(ErrorKind::UnexpectedToken {
+                    expected: String::from("a purr-ameter identifier 🙀"),
+                    found: self.current_token.to_string(),
+                });
                 return None;
             }
         };
@@ -545,7 +978,13 @@ impl Parser {
             self.next_token();
             match self.current_token {
                 Token::Ident(ref mut ident) => idents.push(Ident(ident.clone())),
-                _ => return None,
+                _ => {
+                    self.error_at_current(ErrorKind::UnexpectedToken {
+                        expected: String::from("a purr-ameter identifier 🙀"),
+                        found: self.current_token.to_string(),
+                    });
+                    return None;
+                }
             };
         }
 
@@ -587,20 +1026,57 @@ impl Parser {
         Some(args)
     }
 
+    /// ## parse_index_expr
+    /// Parses `left[index]`, or a slice like `left[start:end:step]` as soon as
+    /// there is a `:` inside the brackets.
     fn parse_index_expr(&mut self, left: Expr) -> Option<Expr> {
+        let start = if self.peek_token(&Token::Colon) {
+            None
+        } else {
+            self.next_token();
+            let index = self.parse_expr(Precedence::Lowest)?;
+            if !self.peek_token(&Token::Colon) {
+                if !self.expect_peek(Token::RightBracket) {
+                    return None;
+                }
+                let span = left.span.to(&self.current_span);
+                return Some(Expr::new(
+                    ExprKind::Index {
+                        array: Box::new(left),
+                        index: Box::new(index),
+                    },
+                    span,
+                ));
+            }
+            Some(Box::new(index))
+        };
+
         self.next_token();
-        let expr = match self.parse_expr(Precedence::Lowest) {
-            Some(e) => e,
-            None => return None,
+        let end = self.parse_slice_bound()?;
+        let step = if self.peek_token(&Token::Colon) {
+            self.next_token();
+            self.parse_slice_bound()?
+        } else {
+            None
         };
         if !self.expect_peek(Token::RightBracket) {
             return None;
         }
 
-        Some(Expr::Index {
-            array: Box::new(left),
-            index: Box::new(expr),
-        })
+        let span = left.span.to(&self.current_span);
+        Some(Expr::new(ExprKind::Slice { array: Box::new(left), start, end, step }, span))
+    }
+
+    /// ## parse_slice_bound
+    /// Parses the bound after a `:` in a slice, with the `:` as the current token.
+    /// # Returns
+    /// `Some(None)` if the bound was left out, `None` if it failed to parse.
+    fn parse_slice_bound(&mut self) -> Option<Option<Box<Expr>>> {
+        if self.peek_token(&Token::Colon) || self.peek_token(&Token::RightBracket) {
+            return Some(None);
+        }
+        self.next_token();
+        self.parse_expr(Precedence::Lowest).map(|e| Some(Box::new(e)))
     }
 
     fn parse_call_expr(&mut self, left: Expr) -> Option<Expr> {
@@ -609,10 +1085,14 @@ impl Parser {
             None => return None,
         };
 
-        Some(Expr::Call {
-            function: Box::new(left),
-            args,
-        })
+        let span = left.span.to(&self.current_span);
+        Some(Expr::new(
+            ExprKind::Call {
+                function: Box::new(left),
+                args,
+            },
+            span,
+        ))
     }
 
     fn peek_token(&self, t: &Token) -> bool {
@@ -623,6 +1103,11 @@ impl Parser {
         self.current_token == t
     }
 
+    /// Returns the span from `start` up to the end of the current token.
+    fn span_from(&self, start: &Span) -> Span {
+        start.to(&self.current_span)
+    }
+
     fn expect_peek(&mut self, t: Token) -> bool {
         if let Token::Ident(..) = t {
             self.next_token();
@@ -639,12 +1124,73 @@ impl Parser {
     }
 
     fn peek_error(&mut self, t: Token) {
-        let msg = format!(
-            "Meowch! Expected {} as the next token, but got {}",
-            t,
-            self.peek_token
-        );
-        self.errors.push(msg);
+        let kind = ErrorKind::UnexpectedToken {
+            expected: format!("{} as the next token", t),
+            found: self.peek_token.to_string(),
+        };
+        self.error_at_peek(kind);
+    }
+
+    /// Consumes the `;` that ends a statement. The last statement of a block or of
+    /// the input can leave it out, anything else is reported as a missing `;`
+    /// and the parser recovers from the next statement boundary.
+    fn end_statement(&mut self) {
+        match self.peek_token {
+            Token::Semicolon => self.next_token(),
+            Token::RightBrace | Token::Eof => (),
+            _ => self.peek_error(Token::Semicolon),
+        }
+    }
+
+    /// ## synchronize
+    /// Skips the rest of a statement that failed to parse.
+    /// It stops on the statement's own `;`, right before a `}` that closes the
+    /// enclosing block or a keyword that starts a new statement, or at the end of the input,
+    /// so the caller's `next_token` lands where parsing can pick up again.
+    fn synchronize(&mut self) {
+        let mut depth = 0;
+        while !self.current_token(Token::Eof) {
+            match self.current_token {
+                Token::Semicolon if depth == 0 => break,
+                Token::LeftBrace => depth += 1,
+                Token::RightBrace if depth > 0 => depth -= 1,
+                _ => (),
+            }
+            let boundary = matches!(
+                self.peek_token,
+                Token::RightBrace
+                    | Token::Eof
+                    | Token::Set
+                    | Token::Anew
+                    | Token::Return
+                    | Token::Include
+                    | Token::Break
+                    | Token::Continue
+                    | Token::Throw
+            );
+            if depth == 0 && boundary {
+                break;
+            }
+            self.next_token();
+        }
+        self.recovering = false;
+    }
+
+    fn error_at_current(&mut self, kind: ErrorKind) {
+        let span = self.current_span.clone();
+        self.error(kind, span);
+    }
+
+    fn error_at_peek(&mut self, kind: ErrorKind) {
+        let span = self.peek_span.clone();
+        self.error(kind, span);
+    }
+
+    fn error(&mut self, kind: ErrorKind, span: Span) {
+        if !self.recovering {
+            self.errors.push(Error::at(kind, span));
+            self.recovering = true;
+        }
     }
 
     fn current_token_precedence(&mut self) -> Precedence {
@@ -654,4 +1200,160 @@ impl Parser {
     fn next_token_precedence(&mut self) -> Precedence {
         Self::token_to_precedence(&self.peek_token)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses `input` as a single expression statement and prints it with every
+    /// operation in parentheses, so the tests can see how it was grouped.
+    fn parse(input: &str) -> String {
+        let mut parser = Parser::new(Lexer::new(input.to_string()));
+        let program = parser.parse_program();
+        assert!(parser.errors.is_empty(), "{} failed to parse: {:?}", input, parser.errors);
+        match &program.statements[..] {
+            [Statement { kind: StatementKind::Expression(expr), .. }] => group(expr),
+            statements => panic!("expected one expression in {}, got {:?}", input, statements),
+        }
+    }
+
+    /// Parses `input` as a whole program and gives back the kinds of the errors it reported.
+    fn errors(input: &str) -> Vec<ErrorKind> {
+        let mut parser = Parser::new(Lexer::new(input.to_string()));
+        parser.parse_program();
+        parser.errors.into_iter().map(|e| e.kind).collect()
+    }
+
+    fn group(expr: &Expr) -> String {
+        match &expr.kind {
+            ExprKind::Literal(Literal::Int(i)) => i.to_string(),
+            ExprKind::Literal(Literal::String(s)) => format!("{:?}", s),
+            ExprKind::Ident(Ident(name)) => name.clone(),
+            ExprKind::Prefix(prefix, right) => format!("({}{})", prefix, group(right)),
+            ExprKind::Infix(infix, left, right) => format!("({} {} {})", group(left), infix, group(right)),
+            ExprKind::Call { function, args } => {
+                let args = args.iter().map(group).collect::<Vec<_>>();
+                format!("{}({})", group(function), args.join(", "))
+            }
+            ExprKind::Index { array, index } => format!("({}[{}])", group(array), group(index)),
+            ExprKind::Typeof { expr } => format!("(furreal {})", group(expr)),
+            ExprKind::Range { start, end, inclusive } => {
+                format!("({} {} {})", group(start), if *inclusive { "..=" } else { ".." }, group(end))
+            }
+            kind => panic!("unexpected expression {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn arithmetic_binds_tighter_than_comparison() {
+        assert_eq!(parse("1 + 2 * 3"), "(1 + (2 * 3))");
+        assert_eq!(parse("1 * 2 + 3"), "((1 * 2) + 3)");
+        assert_eq!(parse("8 / 4 % 3"), "((8 / 4) % 3)");
+        assert_eq!(parse("(1 + 2) * 3"), "((1 + 2) * 3)");
+        assert_eq!(parse("a + b < c * d"), "((a + b) < (c * d))");
+        assert_eq!(parse("a < b == c >= d"), "((a < b) == (c >= d))");
+    }
+
+    #[test]
+    fn binary_operators_are_left_associative() {
+        assert_eq!(parse("1 - 2 - 3"), "((1 - 2) - 3)");
+        assert_eq!(parse("a << b >> c"), "((a << b) >> c)");
+        assert_eq!(parse("a & b & c"), "((a & b) & c)");
+        assert_eq!(parse("a || b || c"), "((a || b) || c)");
+    }
+
+    #[test]
+    fn ranges_bind_between_comparison_and_arithmetic() {
+        assert_eq!(parse("0..n + 1"), "(0 .. (n + 1))");
+        assert_eq!(parse("x ~ 0..=n"), "(x ~ (0 ..= n))");
+        assert_eq!(parse("a..b == c..d"), "((a .. b) == (c .. d))");
+        assert_eq!(parse("arr[1..-1]"), "(arr[(1 .. (-1))])");
+    }
+
+    #[test]
+    fn prefix_operators_bind_tightest() {
+        assert_eq!(parse("-a * b"), "((-a) * b)");
+        assert_eq!(parse("!a == b"), "((!a) == b)");
+        assert_eq!(parse("-1 >> 2"), "((-1) >> 2)");
+        assert_eq!(parse("-f(x)"), "(-f(x))");
+        assert_eq!(parse("furreal x == \"int\""), "((furreal x) == \"int\")");
+    }
+
+    #[test]
+    fn shifts_bind_looser_than_arithmetic() {
+        assert_eq!(parse("x << 1 + 2"), "(x << (1 + 2))");
+        assert_eq!(parse("x >> 2 * 3"), "(x >> (2 * 3))");
+        assert_eq!(parse("a << b & c"), "((a << b) & c)");
+        assert_eq!(parse("a < b << c"), "(a < (b << c))");
+    }
+
+    #[test]
+    fn bitwise_operators_bind_tighter_than_comparison() {
+        assert_eq!(parse("a & b == c"), "((a & b) == c)");
+        assert_eq!(parse("a | b != c"), "((a | b) != c)");
+        assert_eq!(parse("a ^ b < c"), "((a ^ b) < c)");
+        assert_eq!(parse("a | b ^ c & d"), "(a | (b ^ (c & d)))");
+        assert_eq!(parse("a & b | c ^ d"), "((a & b) | (c ^ d))");
+    }
+
+    #[test]
+    fn membership_is_a_comparison() {
+        assert_eq!(parse("a ~ b == c"), "((a ~ b) == c)");
+        assert_eq!(parse("a + 1 ~ b"), "((a + 1) ~ b)");
+        assert_eq!(parse("a ~ b && c"), "((a ~ b) && c)");
+    }
+
+    #[test]
+    fn logical_operators_bind_loosest() {
+        assert_eq!(parse("a || b && c"), "(a || (b && c))");
+        assert_eq!(parse("a && b || c"), "((a && b) || c)");
+        assert_eq!(parse("a == b && c != d"), "((a == b) && (c != d))");
+        assert_eq!(parse("a <= b || c >= d"), "((a <= b) || (c >= d))");
+        assert_eq!(parse("a & b && c | d"), "((a & b) && (c | d))");
+    }
+
+    #[test]
+    fn calls_and_indexes_bind_tighter_than_operators() {
+        assert_eq!(parse("f(a + b) * c"), "(f((a + b)) * c)");
+        assert_eq!(parse("a[1] + b[2]"), "((a[1]) + (b[2]))");
+        assert_eq!(parse("a[i] << f(x)[0]"), "((a[i]) << (f(x)[0]))");
+    }
+
+    #[test]
+    fn lexer_errors_are_reported_alone() {
+        assert_eq!(errors("meow(\"abc);"), vec![ErrorKind::UnterminatedString]);
+        assert_eq!(errors("scratch a = 0x; scratch b = 2;"), vec![ErrorKind::MalformedNumber(String::from("0x"))]);
+    }
+
+    #[test]
+    fn jumps_can_end_a_block() {
+        assert_eq!(errors("furrever { hiss }"), vec![]);
+        assert_eq!(errors("furrever { continue }"), vec![]);
+        assert_eq!(errors("'outer: furrever { hiss 'outer 1 }"), vec![]);
+        assert_eq!(errors("furrever { hiss"), vec![ErrorKind::UnexpectedToken {
+            expected: String::from("RightBrace to close the block"),
+            found: String::from("Eof"),
+        }]);
+    }
+
+    #[test]
+    fn jumps_need_a_loop_to_jump_to() {
+        assert_eq!(errors("hiss;"), vec![ErrorKind::OutsideLoop(String::from("hiss"))]);
+        assert_eq!(errors("furrever { pawction() { continue; }; }"), vec![ErrorKind::OutsideLoop(String::from("continue"))]);
+        assert_eq!(errors("'a: furrever { hiss 'b; }"), vec![ErrorKind::UnknownLabel(String::from("b"))]);
+        assert_eq!(errors("'a: furrever { pawction() { furrever { hiss 'a; } }; }"), vec![ErrorKind::UnknownLabel(String::from("a"))]);
+    }
+
+    #[test]
+    fn statements_end_with_a_semicolon() {
+        assert_eq!(
+            errors("scratch x = 1 + 2 3; meow(x);"),
+            vec![ErrorKind::UnexpectedToken {
+                expected: String::from("Semicolon as the next token"),
+                found: String::from("Int(3)"),
+            }]
+        );
+        assert_eq!(errors("pawction() { tail }; pawction() { tail 1 }; scratch x = 1"), vec![]);
+    }
+}
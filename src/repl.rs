@@ -1,4 +1,5 @@
 use crate::{
+    diagnostic::{render, Sources},
     evaluation::{store::Store, object::Object, Eval},
     lexer::Lexer,
     parser::Parser,
@@ -11,9 +12,11 @@ use std::{
 
 pub fn start() {
     let env = Store::new();
-    let mut evaluator = Eval {
-        store: Rc::new(RefCell::new(env)),
-    };
+    let mut evaluator = Eval::new(Rc::new(RefCell::new(env)));
+    let mut sources = Sources::new();
+    let mut line = 0;
+    // Doc comment lines typed so far, kept for the binding on the next line.
+    let mut doc = String::new();
     loop {
         print!(">> ");
         let _ = stdout().flush();
@@ -25,20 +28,39 @@ pub fn start() {
             continue;
         }
 
-        let lexer = Lexer::new(input_string);
+        if input_string.trim_start().starts_with("///") {
+            doc.push_str(&input_string);
+            continue;
+        }
+        let input_string = std::mem::take(&mut doc) + &input_string;
+
+        // Every input gets its own name so functions defined earlier
+        // still point at the line they were typed on.
+        line += 1;
+        let file = format!("<repl:{}>", line);
+        sources.add(&file, &input_string);
+
+        if let Some(name) = input_string.trim().strip_prefix("help ") {
+            let name = name.trim();
+            match evaluator.store.borrow().doc(name) {
+                Some(doc) => println!("{}", doc),
+                None => println!("No docs for '{}'. Add a /// comment above its scratch.", name),
+            }
+            continue;
+        }
+
+        let lexer = Lexer::with_file(input_string, &file);
         let mut parser = Parser::new(lexer);
         let program = parser.parse_program();
         if !parser.errors.is_empty() {
-            print_parse_errors(parser.errors);
+            for e in parser.errors.iter() {
+                println!("{}", render(e, &mut sources));
+            }
             continue;
         }
-        let res = evaluator.eval(program);
-        println!("{}", res.unwrap_or(Object::Null));
-    }
-}
-
-fn print_parse_errors(errors: Vec<String>) {
-    for e in errors.iter() {
-        println!("\t{}", e);
+        match evaluator.eval(program).unwrap_or(Object::Null) {
+            Object::Error(e) => println!("{}", render(&e, &mut sources)),
+            res => println!("{}", res),
+        }
     }
 }
\ No newline at end of file
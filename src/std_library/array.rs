@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::evaluation::object::Object;
+use crate::{error::ErrorKind, evaluation::object::Object};
 
 use super::Res;
 
@@ -12,6 +12,7 @@ pub fn add_globals() -> Res {
     globals.insert(String::from("bottom"), Object::Inbuilt(tail));
     globals.insert(String::from("push"), Object::Inbuilt(push));
     globals.insert(String::from("includes"), Object::Inbuilt(includes));
+    globals.insert(String::from("to_array"), Object::Inbuilt(to_array));
     Res {
         globals,
         raw: Some("
@@ -35,86 +36,87 @@ pub fn add_globals() -> Res {
 }
 
 /// The std:array-built-in function `push`.
-/// Pushes an object onto the end of an array.
+/// Pushes an object onto the end of an array, changing it in place.
 /// # Arguments
 /// * `args` - The array to push onto.
+/// # Returns
+/// The same array, so `amew arr = push(arr, x);` keeps working.
 pub fn push(args: Vec<Object>) -> Object {
     if args.len() != 2 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 2.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 2, found: args.len() }.into());
     }
 
     match &args[0] {
         Object::Array(a) => {
-            let mut array = a.clone();
-            array.push(args[1].clone());
-            Object::Array(array)
+            a.borrow_mut().push(args[1].clone());
+            args[0].clone()
         }
-        o => Object::Error(format!("First argument must be an array. Got {}", o)),
+        o => Object::Error(ErrorKind::TypeMismatch(format!("first argument must be an array, got {}", o)).into()),
     }
 }
 
+/// The std:array-built-in function `pounce`.
+/// Removes the last object of an array, changing it in place.
+/// # Returns
+/// The same array.
 pub fn pop(args: Vec<Object>) -> Object {
     if args.len() != 1 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 1.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 1, found: args.len() }.into());
     }
     match &args[0] {
         Object::Array(a) => {
-            let mut array = a.clone();
-            array.pop();
-            Object::Array(array)
+            a.borrow_mut().pop();
+            args[0].clone()
         }
-        o => Object::Error(format!("First argument must be an array. Got {}", o)),
+        o => Object::Error(ErrorKind::TypeMismatch(format!("first argument must be an array, got {}", o)).into()),
     }
 }
 
 pub fn head (args: Vec<Object>) -> Object {
     if args.len() != 1 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 1.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 1, found: args.len() }.into());
     }
     match &args[0] {
         Object::Array(a) => {
-            let mut array = a.clone();
+            let mut array = a.borrow().clone();
             array.pop();
-            Object::Array(array)
+            Object::array(array)
         }
-        o => Object::Error(format!("First argument must be an array. Got {}", o)),
+        o => Object::Error(ErrorKind::TypeMismatch(format!("first argument must be an array, got {}", o)).into()),
     }
 }
 
 pub fn tail (args: Vec<Object>) -> Object {
     if args.len() != 1 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 1.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 1, found: args.len() }.into());
     }
     match &args[0] {
-        Object::Array(a) => Object::Array(a[1..].to_vec()),
-        o => Object::Error(format!("First argument must be an array. Got {}", o)),
+        Object::Array(a) => Object::array(a.borrow().iter().skip(1).cloned().collect()),
+        o => Object::Error(ErrorKind::TypeMismatch(format!("first argument must be an array, got {}", o)).into()),
     }
 }
 
 pub fn includes (args: Vec<Object>) -> Object {
     if args.len() != 2 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 2.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 2, found: args.len() }.into());
     }
     match &args[0] {
-        Object::Array(a) => {
-            let array = a.clone();
-            Object::Bool(array.contains(&args[1]))
-        }
-        o => Object::Error(format!("First argument must be an array. Got {}", o)),
+        Object::Array(a) => Object::Bool(a.borrow().contains(&args[1])),
+        o => Object::Error(ErrorKind::TypeMismatch(format!("first argument must be an array, got {}", o)).into()),
+    }
+}
+
+/// The std:array-built-in function `to_array`.
+/// Turns a range into an array of its integers and a string into an array of its characters.
+/// An array is copied, so the copy can be changed without changing the original.
+pub fn to_array(args: Vec<Object>) -> Object {
+    if args.len() != 1 {
+        return Object::Error(ErrorKind::Arity { expected: 1, found: args.len() }.into());
     }
-}
\ No newline at end of file
+    match &args[0] {
+        Object::Range(start, end, inclusive) => Object::array(Object::ints(*start, *end, *inclusive).map(Object::Int).collect()),
+        Object::String(s) => Object::array(s.chars().map(|c| Object::String(c.to_string())).collect()),
+        Object::Array(a) => Object::array(a.borrow().clone()),
+        o => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a range, string or array, got {}", o)).into()),
+    }
+}
@@ -3,7 +3,7 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
 
-use crate::evaluation::object::Object;
+use crate::{error::ErrorKind, evaluation::object::Object};
 
 use super::Res;
 
@@ -21,10 +21,7 @@ pub fn add_globals() -> Res {
 
 pub fn read_file(args: Vec<Object>) -> Object {
     if args.len() != 1 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 1.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 1, found: args.len() }.into());
     }
 
     match &args[0] {
@@ -33,30 +30,27 @@ pub fn read_file(args: Vec<Object>) -> Object {
             let display = path.display();
             let mut file = match File::open(&path) {
                 Err(why) => {
-                    return Object::Error(format!("Couldn't open {}: {}", display, why))
+                    return Object::Error(ErrorKind::IoError(format!("Couldn't open {}: {}", display, why)).into())
                 }
                 Ok(file) => file,
             };
             let mut s = String::new();
             match file.read_to_string(&mut s) {
                 Err(why) => {
-                    return Object::Error(format!("Couldn't read {}: {}", display, why))
+                    return Object::Error(ErrorKind::IoError(format!("Couldn't read {}: {}", display, why)).into())
                 }
                 Ok(_) => {
                     Object::String(s)
                 }
             }
         }
-        _ => Object::Error(format!("Argument must be a string. Got {}", args[0]))
+        _ => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a string, got {}", args[0])).into())
     }
 }
 
 pub fn write_file(args: Vec<Object>) -> Object {
     if args.len() != 2 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 2.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 2, found: args.len() }.into());
     }
     match &args[0] {
         Object::String(s) => {
@@ -64,29 +58,26 @@ pub fn write_file(args: Vec<Object>) -> Object {
             let display = path.display();
             let mut file = match File::create(&path) {
                 Err(why) => {
-                    return Object::Error(format!("Couldn't create {}: {}", display, why))
+                    return Object::Error(ErrorKind::IoError(format!("Couldn't create {}: {}", display, why)).into())
                 }
                 Ok(file) => file,
             };
             match file.write_all(args[1].to_string().as_bytes()) {
                 Err(why) => {
-                    return Object::Error(format!("Couldn't write to {}: {}", display, why))
+                    return Object::Error(ErrorKind::IoError(format!("Couldn't write to {}: {}", display, why)).into())
                 }
                 Ok(_) => {
                     Object::Null
                 }
             }
         }
-        _ => Object::Error(format!("Argument must be a string. Got {}", args[0]))
+        _ => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a string, got {}", args[0])).into())
     }
 }
 
 pub fn file_exists(args: Vec<Object>) -> Object {
     if args.len() != 1 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 1.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 1, found: args.len() }.into());
     }
     match &args[0] {
         Object::String(s) => {
@@ -97,7 +88,7 @@ pub fn file_exists(args: Vec<Object>) -> Object {
                 Object::Bool(false)
             }
         }
-        _ => Object::Error(format!("Argument must be a string. Got {}", args[0]))
+        _ => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a string, got {}", args[0])).into())
     }
 }
 
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Signed, Zero};
 use rand::Rng;
 
-use crate::evaluation::object::Object;
+use crate::{error::ErrorKind, evaluation::object::Object};
 
 use super::Res;
 
@@ -21,99 +23,110 @@ pub fn add_globals() -> Res {
     globals.insert(String::from("log2"), Object::Inbuilt(log2));
     globals.insert(String::from("log10"), Object::Inbuilt(log10));
     globals.insert(String::from("modulo"), Object::Inbuilt(modulo));
+    globals.insert(String::from("parse_int"), Object::Inbuilt(parse_int));
     globals.insert(String::from("Math.PI"), Object::Number(std::f64::consts::PI));
     globals.insert(String::from("Math.E"), Object::Number(std::f64::consts::E));
-    globals.insert(String::from("MAX_INT"), Object::Number(std::f64::MAX));
-    globals.insert(String::from("MIN_INT"), Object::Number(std::f64::MIN));
+    globals.insert(String::from("MAX_INT"), Object::Int(i64::MAX));
+    globals.insert(String::from("MIN_INT"), Object::Int(i64::MIN));
     Res {
         globals,
         raw: None,
     }
 }
 
+/// Picks a random number from `min` up to, but not including, `max`.
+/// Gives an integer when both bounds are integers.
 pub fn random(args: Vec<Object>) -> Object {
-    let min = match &args[0] {
-        Object::Number(n) => *n,
-        _ => 0.0,
-    };
-
-    let max = match &args[1] {
-        Object::Number(n) => *n,
-        _ => 0.0,
-    };
-
+    if args.len() != 2 {
+        return Object::Error(ErrorKind::Arity { expected: 2, found: args.len() }.into());
+    }
     let mut rng = rand::thread_rng();
-    let random_number = rng.gen_range(min..max);
-    Object::Number(random_number)
+    match (&args[0], &args[1]) {
+        (Object::Int(min), Object::Int(max)) if min < max => Object::Int(rng.gen_range(*min..*max)),
+        (min, max) => match (min.as_f64(), max.as_f64()) {
+            (Some(min), Some(max)) if min < max => Object::Number(rng.gen_range(min..max)),
+            _ => Object::Error(ErrorKind::TypeMismatch(format!(
+                "random needs two numbers with min below max, got {} and {}",
+                min, max
+            )).into()),
+        },
+    }
 }
 
 pub fn round(args: Vec<Object>) -> Object {
     match &args[0] {
+        Object::Int(_) | Object::BigInt(_) => args[0].clone(),
         Object::Number(n) => Object::Number(n.round()),
-        _ => Object::Error(format!("Argument must be a number. Got {}", args[0])),
+        _ => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a number, got {}", args[0])).into()),
     }
 }
 
 pub fn log2(args: Vec<Object>) -> Object {
-    match &args[0] {
-        Object::Number(n) => Object::Number(n.log2()),
-        _ => Object::Error(format!("Argument must be a number. Got {}", args[0])),
+    match args[0].as_f64() {
+        Some(n) => Object::Number(n.log2()),
+        None => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a number, got {}", args[0])).into()),
     }
 }
 
 pub fn log10(args: Vec<Object>) -> Object {
-    match &args[0] {
-        Object::Number(n) => Object::Number(n.log10()),
-        _ => Object::Error(format!("Argument must be a number. Got {}", args[0])),
+    match args[0].as_f64() {
+        Some(n) => Object::Number(n.log10()),
+        None => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a number, got {}", args[0])).into()),
     }
 }
 
 pub fn sin(args: Vec<Object>) -> Object {
-    match &args[0] {
-        Object::Number(n) => Object::Number(n.sin()),
-        _ => Object::Error(format!("Argument must be a number. Got {}", args[0])),
+    match args[0].as_f64() {
+        Some(n) => Object::Number(n.sin()),
+        None => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a number, got {}", args[0])).into()),
     }
 }
 
 pub fn cos(args: Vec<Object>) -> Object {
-    match &args[0] {
-        Object::Number(n) => Object::Number(n.cos()),
-        _ => Object::Error(format!("Argument must be a number. Got {}", args[0])),
+    match args[0].as_f64() {
+        Some(n) => Object::Number(n.cos()),
+        None => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a number, got {}", args[0])).into()),
     }
 }
 
 pub fn tan(args: Vec<Object>) -> Object {
-    match &args[0] {
-        Object::Number(n) => Object::Number(n.tan()),
-        _ => Object::Error(format!("Argument must be a number. Got {}", args[0])),
+    match args[0].as_f64() {
+        Some(n) => Object::Number(n.tan()),
+        None => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a number, got {}", args[0])).into()),
     }
 }
 
+/// Raises `base` to the power `to`.
+/// An integer raised to a non-negative integer power gives an exact integer,
+/// which becomes a big integer when it does not fit in 64 bits. Anything else gives a float.
+/// An integer result too big to fit in memory is an error.
 pub fn pow(args: Vec<Object>) -> Object {
-    let base = match &args[0] {
-        Object::Number(n) => *n,
-        _ => 0.0,
-    };
+    if let (Some(base), Object::Int(to)) = (args[0].as_big_int(), &args[1]) {
+        if let Ok(to) = u32::try_from(*to) {
+            if base.magnitude() > &BigUint::one() && base.bits() * to as u64 > Object::MAX_INTEGER_BITS {
+                return Object::Error(ErrorKind::Arithmetic(format!("pow({}, {}) is too big to compute", base, to)).into());
+            }
+            return Object::integer(base.pow(to));
+        }
+    }
 
-    let to = match &args[1] {
-        Object::Number(n) => *n,
-        _ => 0.0,
-    };
+    let base = args[0].as_f64().unwrap_or(0.0);
+    let to = args[1].as_f64().unwrap_or(0.0);
 
     let result = f64::powf(base, to);
     Object::Number(result)
 }
 
+/// The remainder of `a / b` with the sign of `b`, so `modulo(-1, 3)` is `2`.
 pub fn modulo(args: Vec<Object>) -> Object {
-    let a = match &args[0] {
-        Object::Number(n) => *n,
-        _ => 0.0,
-    };
+    if let (Some(a), Some(b)) = (args[0].as_big_int(), args[1].as_big_int()) {
+        if !b.is_zero() {
+            return Object::integer(((a % &b) + &b) % b);
+        }
+    }
 
-    let b = match &args[1] {
-        Object::Number(n) => *n,
-        _ => 0.0,
-    };
+    let a = args[0].as_f64().unwrap_or(0.0);
+    let b = args[1].as_f64().unwrap_or(0.0);
 
     let mut result = a % b;
     result = result + b;
@@ -125,28 +138,47 @@ pub fn modulo(args: Vec<Object>) -> Object {
 
 pub fn floor(args: Vec<Object>) -> Object {
     match &args[0] {
+        Object::Int(_) | Object::BigInt(_) => args[0].clone(),
         Object::Number(n) => Object::Number(n.floor()),
-        _ => Object::Error(format!("Argument must be a number. Got {}", args[0])),
+        _ => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a number, got {}", args[0])).into()),
     }
 }
 
 pub fn ceil(args: Vec<Object>) -> Object {
     match &args[0] {
+        Object::Int(_) | Object::BigInt(_) => args[0].clone(),
         Object::Number(n) => Object::Number(n.ceil()),
-        _ => Object::Error(format!("Argument must be a number. Got {}", args[0])),
+        _ => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a number, got {}", args[0])).into()),
     }
 }
 
 pub fn abs(args: Vec<Object>) -> Object {
     match &args[0] {
+        Object::Int(i) => i.checked_abs().map_or_else(|| Object::integer(BigInt::from(*i).abs()), Object::Int),
+        Object::BigInt(i) => Object::integer(i.abs()),
         Object::Number(n) => Object::Number(n.abs()),
-        _ => Object::Error(format!("Argument must be a number. Got {}", args[0])),
+        _ => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a number, got {}", args[0])).into()),
     }
 }
 
 pub fn sqrt(args: Vec<Object>) -> Object {
+    match args[0].as_f64() {
+        Some(n) => Object::Number(n.sqrt()),
+        None => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a number, got {}", args[0])).into()),
+    }
+}
+
+/// Parses a string of decimal digits, with an optional sign, into an integer.
+/// Numbers too big for 64 bits become big integers.
+pub fn parse_int(args: Vec<Object>) -> Object {
+    if args.len() != 1 {
+        return Object::Error(ErrorKind::Arity { expected: 1, found: args.len() }.into());
+    }
     match &args[0] {
-        Object::Number(n) => Object::Number(n.sqrt()),
-        _ => Object::Error(format!("Argument must be a number. Got {}", args[0])),
+        Object::String(s) => match s.trim().parse::<BigInt>() {
+            Ok(n) => Object::integer(n),
+            Err(_) => Object::Error(ErrorKind::TypeMismatch(format!("'{}' is not an integer", s)).into()),
+        },
+        o => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a string, got {}", o)).into()),
     }
 }
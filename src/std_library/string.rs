@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::evaluation::object::Object;
+use crate::{error::ErrorKind, evaluation::object::Object};
 
 use super::Res;
 
@@ -17,10 +17,7 @@ pub fn add_globals() -> Res {
 
 pub fn replace(args: Vec<Object>) -> Object {
     if args.len() != 3 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 3.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 3, found: args.len() }.into());
     }
 
     match &args[0] {
@@ -29,31 +26,29 @@ pub fn replace(args: Vec<Object>) -> Object {
             s = s.replace(&args[1].to_string(), &args[2].to_string());
             Object::String(s)
         }
-        o => Object::Error(format!("First argument must be a string. Got {}", o)),
+        o => Object::Error(ErrorKind::TypeMismatch(format!("first argument must be a string, got {}", o)).into()),
     }
 }
 
 pub fn to_string(args: Vec<Object>) -> Object {
     if args.len() != 1 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 1.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 1, found: args.len() }.into());
     }
 
     match &args[0] {
         Object::String(s) => Object::String(s.clone()),
         Object::Array(a) => {
             let mut s = String::new();
-            for o in a.iter() {
+            for o in a.borrow().iter() {
                 s.push_str(&o.to_string());
             }
             Object::String(s)
         },
+        Object::Int(n) => Object::String(n.to_string()),
         Object::Number(n) => Object::String(n.to_string()),
         Object::Bool(b) => Object::String(b.to_string()),
         Object::Null => Object::String(String::from("null")),
-        Object::Error(e) => Object::String(e.clone()),
+        Object::Error(e) => Object::String(e.message()),
         Object::Fn(..) => Object::String(String::from("[Function]")),
         Object::Inbuilt(..) => Object::String(String::from("[Inbuilt Function]")),
         o => Object::String(format!("{}", o))        
@@ -1,5 +1,6 @@
 use std::{format, collections::HashMap};
-use crate::evaluation::object::Object;
+use crate::{error::ErrorKind, evaluation::object::Object};
+use num_bigint::BigInt;
 
 use super::Res;
 
@@ -11,23 +12,27 @@ pub fn add_globals() -> Res {
     Res { globals, raw: None }
 }
 
-/// Function to get the length of an array or string.
+/// Function to get the length of an array, string or range.
 /// # Arguments
-/// * `args` - The array or string to get the length of.
+/// * `args` - The array, string or range to get the length of.
 /// # Returns
-/// `Object` - The length of the array or string.
+/// `Object` - The length of the array, string or range.
 pub fn length(args: Vec<Object>) -> Object {
     if args.len() != 1 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 1.",
-            args.len()
-            
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 1, found: args.len() }.into());
     }
     match &args[0] {
-        Object::String(s) => Object::Number(s.len() as f64),
-        Object::Array(a) => Object::Number(a.len() as f64),
-        o => Object::Error(format!("Argument must be a string or array. Got {}", o)),
+        Object::String(s) => Object::Int(s.chars().count() as i64),
+        Object::Array(a) => Object::Int(a.borrow().len() as i64),
+        Object::Range(start, end, inclusive) => {
+            let ints = Object::ints(*start, *end, *inclusive);
+            if ints.is_empty() {
+                Object::Int(0)
+            } else {
+                Object::integer(BigInt::from(*ints.end()) - *ints.start() + 1)
+            }
+        }
+        o => Object::Error(ErrorKind::TypeMismatch(format!("argument must be a string, array or range, got {}", o)).into()),
     }
 }
 
@@ -43,13 +48,10 @@ pub fn kibble(args: Vec<Object>) -> Object {
 
 pub fn nap(args: Vec<Object>) -> Object {
     if args.len() != 1 {
-        return Object::Error(format!(
-            "Wrong number of arguments. Got {}. Expected 1.",
-            args.len()
-        ));
+        return Object::Error(ErrorKind::Arity { expected: 1, found: args.len() }.into());
     }
-    if let Object::Number(n) = &args[0] {
-        std::thread::sleep(std::time::Duration::from_millis(*n as u64));
+    if let Some(n) = args[0].as_f64() {
+        std::thread::sleep(std::time::Duration::from_millis(n as u64));
     }
     Object::Null
 }
\ No newline at end of file